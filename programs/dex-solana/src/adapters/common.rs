@@ -1,4 +1,5 @@
 use crate::error::ErrorCode;
+use crate::utils::{transfer_sol_from_authority, wrap_sol_from_authority};
 use crate::{authority_pda, HopAccounts, BUMP_SA, SEED_SA, ZERO_ADDRESS};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
@@ -17,6 +18,61 @@ pub trait DexProcessor {
     }
 }
 
+// For dexes taking native SOL from the swap authority: the wSOL input is unwrapped into the
+// authority before the invoke, afterwards the temporary account rent goes back to the payer
+// and the unspent lamports are wrapped back into the source token account
+pub struct SolInProcessor<'info> {
+    pub authority: AccountInfo<'info>,
+    pub source_token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub authority_lamports: u64,
+    pub rent_paid: u64,
+}
+
+impl DexProcessor for SolInProcessor<'_> {
+    fn after_invoke(&self, _account_infos: &[AccountInfo]) -> Result<u64> {
+        transfer_sol_from_authority(self.authority.clone(), self.payer.clone(), self.rent_paid)?;
+        let refund = self
+            .authority
+            .lamports()
+            .checked_sub(self.authority_lamports)
+            .ok_or(ErrorCode::CalculationError)?;
+        wrap_sol_from_authority(
+            self.authority.clone(),
+            self.source_token_account.clone(),
+            self.token_program.clone(),
+            refund,
+        )?;
+        Ok(refund)
+    }
+}
+
+// For dexes paying native SOL to the swap authority: the received lamports are wrapped into
+// the wSOL destination token account
+pub struct SolOutProcessor<'info> {
+    pub authority: AccountInfo<'info>,
+    pub destination_token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub authority_lamports: u64,
+}
+
+impl DexProcessor for SolOutProcessor<'_> {
+    fn after_invoke(&self, _account_infos: &[AccountInfo]) -> Result<u64> {
+        let sol_amount_out = self
+            .authority
+            .lamports()
+            .checked_sub(self.authority_lamports)
+            .ok_or(ErrorCode::CalculationError)?;
+        wrap_sol_from_authority(
+            self.authority.clone(),
+            self.destination_token_account.clone(),
+            self.token_program.clone(),
+            sol_amount_out,
+        )?;
+        Ok(sol_amount_out)
+    }
+}
 
 pub fn before_check(
     swap_authority_pubkey: &AccountInfo,
@@ -98,3 +154,31 @@ pub fn invoke_process<'info, T: DexProcessor>(
         .ok_or(ErrorCode::CalculationError)?;
     Ok(amount_out)
 }
+
+// For order books filling only part of amount_in: report the amount left in the source account,
+// so the router can forward it to the next split
+pub fn update_amount_in_remainder<'info>(
+    swap_source_account: &mut InterfaceAccount<'info, TokenAccount>,
+    before_source_balance: u64,
+    amount_in: u64,
+    hop_accounts: &mut HopAccounts,
+) -> Result<()> {
+    swap_source_account.reload()?;
+    let amount_in_used = before_source_balance
+        .checked_sub(swap_source_account.amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    hop_accounts.amount_in_remainder = amount_in
+        .checked_sub(amount_in_used)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(())
+}
+
+// Intermediate accounts are shared by all swaps, so later hops may only leave the dust the last
+// dex can't fill in them
+pub fn check_amount_in_remainder(hop: usize, hop_accounts: &HopAccounts) -> Result<()> {
+    require!(
+        hop == 0 || hop_accounts.amount_in_remainder <= hop_accounts.amount_in_dust_limit,
+        ErrorCode::AmountInRemainderNotRefunded
+    );
+    Ok(())
+}
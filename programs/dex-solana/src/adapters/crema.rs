@@ -0,0 +1,233 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{crema_program, HopAccounts, SWAP_SELECTOR};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::TokenAccount;
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 42;
+const MIN_SQRT_PRICE: u128 = 4295048016;
+const MAX_SQRT_PRICE: u128 = 79226673515401279992447579055;
+
+pub struct CremaProcessor;
+impl DexProcessor for CremaProcessor {}
+
+pub struct CremaAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub clmm_config: &'info AccountInfo<'info>,
+    pub clmmpool: &'info AccountInfo<'info>,
+    pub token_a_mint: &'info AccountInfo<'info>,
+    pub token_b_mint: &'info AccountInfo<'info>,
+    pub token_a_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_b_vault: InterfaceAccount<'info, TokenAccount>,
+    pub tick_array_map: &'info AccountInfo<'info>,
+    pub partner: &'info AccountInfo<'info>,
+    pub partner_ata_a: &'info AccountInfo<'info>,
+    pub partner_ata_b: &'info AccountInfo<'info>,
+    pub token_program: &'info AccountInfo<'info>,
+    pub tick_arrays: &'info [AccountInfo<'info>],
+}
+// Followed by the tick arrays, three unless the route carries the accounts count
+pub const FIXED_ACCOUNTS_LEN: usize = 15;
+pub const ACCOUNTS_LEN: usize = 18;
+pub const MAX_TICK_ARRAYS_LEN: usize = 10;
+pub const MAX_ACCOUNTS_LEN: usize = FIXED_ACCOUNTS_LEN + MAX_TICK_ARRAYS_LEN;
+
+impl<'info> CremaAccounts<'info> {
+    fn parse_accounts(
+        accounts: &'info [AccountInfo<'info>],
+        offset: usize,
+        accounts_len: usize,
+    ) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            clmm_config,
+            clmmpool,
+            token_a_mint,
+            token_b_mint,
+            token_a_vault,
+            token_b_vault,
+            tick_array_map,
+            partner,
+            partner_ata_a,
+            partner_ata_b,
+            token_program,
+        ]: &[AccountInfo<'info>; FIXED_ACCOUNTS_LEN] =
+            array_ref![accounts, offset, FIXED_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            clmm_config,
+            clmmpool,
+            token_a_mint,
+            token_b_mint,
+            token_a_vault: InterfaceAccount::try_from(token_a_vault)?,
+            token_b_vault: InterfaceAccount::try_from(token_b_vault)?,
+            tick_array_map,
+            partner,
+            partner_ata_a,
+            partner_ata_b,
+            token_program,
+            tick_arrays: &accounts[offset + FIXED_ACCOUNTS_LEN..offset + accounts_len],
+        })
+    }
+}
+
+pub fn swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    accounts_len: Option<usize>,
+) -> Result<u64> {
+    msg!("Dex::Crema amount_in: {}, offset: {}", amount_in, offset);
+    let accounts_len = accounts_len.unwrap_or(ACCOUNTS_LEN);
+    require!(
+        accounts_len > FIXED_ACCOUNTS_LEN && remaining_accounts.len() >= *offset + accounts_len,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts =
+        CremaAccounts::parse_accounts(remaining_accounts, *offset, accounts_len)?;
+    if swap_accounts.dex_program_id.key != &crema_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    let (a_to_b, account_a, account_b) = if swap_accounts.swap_source_token.mint
+        == swap_accounts.token_a_vault.mint
+        && swap_accounts.swap_destination_token.mint == swap_accounts.token_b_vault.mint
+    {
+        (true, swap_source_token, swap_destination_token)
+    } else if swap_accounts.swap_source_token.mint == swap_accounts.token_b_vault.mint
+        && swap_accounts.swap_destination_token.mint == swap_accounts.token_a_vault.mint
+    {
+        (false, swap_destination_token, swap_source_token)
+    } else {
+        return Err(ErrorCode::InvalidTokenMint.into());
+    };
+    let sqrt_price_limit = if a_to_b {
+        MIN_SQRT_PRICE
+    } else {
+        MAX_SQRT_PRICE
+    };
+    let amount_specified_is_input = true;
+    let amount_limit = 1u64;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(SWAP_SELECTOR);
+    data.extend_from_slice(&(a_to_b as u8).to_le_bytes());
+    data.extend_from_slice(&(amount_specified_is_input as u8).to_le_bytes());
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&amount_limit.to_le_bytes());
+    data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.clmm_config.key(), false),
+        AccountMeta::new(swap_accounts.clmmpool.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_a_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_b_mint.key(), false),
+        AccountMeta::new(account_a, false),
+        AccountMeta::new(account_b, false),
+        AccountMeta::new(swap_accounts.token_a_vault.key(), false),
+        AccountMeta::new(swap_accounts.token_b_vault.key(), false),
+        AccountMeta::new(swap_accounts.tick_array_map.key(), false),
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.partner.key(), false),
+        AccountMeta::new(swap_accounts.partner_ata_a.key(), false),
+        AccountMeta::new(swap_accounts.partner_ata_b.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+    ];
+
+    let mut account_infos = vec![
+        swap_accounts.clmm_config.to_account_info(),
+        swap_accounts.clmmpool.to_account_info(),
+        swap_accounts.token_a_mint.to_account_info(),
+        swap_accounts.token_b_mint.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.token_a_vault.to_account_info(),
+        swap_accounts.token_b_vault.to_account_info(),
+        swap_accounts.tick_array_map.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.partner.to_account_info(),
+        swap_accounts.partner_ata_a.to_account_info(),
+        swap_accounts.partner_ata_b.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+    ];
+
+    for tick_array in swap_accounts.tick_arrays.iter() {
+        accounts.push(AccountMeta::new(tick_array.key(), false));
+        account_infos.push(tick_array.to_account_info());
+    }
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &CremaProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        accounts_len,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_swap_instruction() {
+        let amount_in = 100u64;
+        let a_to_b = true;
+        let amount_specified_is_input = true;
+        let amount_limit = 1u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(SWAP_SELECTOR);
+        data.extend_from_slice(&(a_to_b as u8).to_le_bytes());
+        data.extend_from_slice(&(amount_specified_is_input as u8).to_le_bytes());
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&amount_limit.to_le_bytes());
+        data.extend_from_slice(&MIN_SQRT_PRICE.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
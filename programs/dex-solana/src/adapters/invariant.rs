@@ -0,0 +1,227 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{invariant_program, HopAccounts, SWAP_SELECTOR};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 34;
+// Invariant prices carry 24 decimals
+const MIN_SQRT_PRICE: u128 = 15258932000000000000;
+const MAX_SQRT_PRICE: u128 = 65535383934512647000000000000;
+
+pub struct InvariantProcessor;
+impl DexProcessor for InvariantProcessor {}
+
+pub struct InvariantAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub state: &'info AccountInfo<'info>,
+    pub pool: &'info AccountInfo<'info>,
+    pub tickmap: &'info AccountInfo<'info>,
+    pub token_x: InterfaceAccount<'info, Mint>,
+    pub token_y: InterfaceAccount<'info, Mint>,
+    pub reserve_x: InterfaceAccount<'info, TokenAccount>,
+    pub reserve_y: InterfaceAccount<'info, TokenAccount>,
+    pub program_authority: &'info AccountInfo<'info>,
+    pub token_x_program: &'info AccountInfo<'info>,
+    pub token_y_program: &'info AccountInfo<'info>,
+    pub ticks: &'info [AccountInfo<'info>],
+}
+// Followed by the crossed ticks when the route carries the accounts count
+pub const ACCOUNTS_LEN: usize = 14;
+pub const MAX_TICKS_LEN: usize = 10;
+pub const MAX_ACCOUNTS_LEN: usize = ACCOUNTS_LEN + MAX_TICKS_LEN;
+
+impl<'info> InvariantAccounts<'info> {
+    fn parse_accounts(
+        accounts: &'info [AccountInfo<'info>],
+        offset: usize,
+        accounts_len: usize,
+    ) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            state,
+            pool,
+            tickmap,
+            token_x,
+            token_y,
+            reserve_x,
+            reserve_y,
+            program_authority,
+            token_x_program,
+            token_y_program,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            state,
+            pool,
+            tickmap,
+            token_x: InterfaceAccount::try_from(token_x)?,
+            token_y: InterfaceAccount::try_from(token_y)?,
+            reserve_x: InterfaceAccount::try_from(reserve_x)?,
+            reserve_y: InterfaceAccount::try_from(reserve_y)?,
+            program_authority,
+            token_x_program,
+            token_y_program,
+            ticks: &accounts[offset + ACCOUNTS_LEN..offset + accounts_len],
+        })
+    }
+}
+
+pub fn swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    accounts_len: Option<usize>,
+) -> Result<u64> {
+    msg!(
+        "Dex::Invariant amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    let accounts_len = accounts_len.unwrap_or(ACCOUNTS_LEN);
+    require!(
+        accounts_len >= ACCOUNTS_LEN && remaining_accounts.len() >= *offset + accounts_len,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts =
+        InvariantAccounts::parse_accounts(remaining_accounts, *offset, accounts_len)?;
+    if swap_accounts.dex_program_id.key != &invariant_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    let (x_to_y, account_x, account_y) = if swap_accounts.swap_source_token.mint
+        == swap_accounts.reserve_x.mint
+        && swap_accounts.swap_destination_token.mint == swap_accounts.reserve_y.mint
+    {
+        (true, swap_source_token, swap_destination_token)
+    } else if swap_accounts.swap_source_token.mint == swap_accounts.reserve_y.mint
+        && swap_accounts.swap_destination_token.mint == swap_accounts.reserve_x.mint
+    {
+        (false, swap_destination_token, swap_source_token)
+    } else {
+        return Err(ErrorCode::InvalidTokenMint.into());
+    };
+    let sqrt_price_limit = if x_to_y {
+        MIN_SQRT_PRICE
+    } else {
+        MAX_SQRT_PRICE
+    };
+    let by_amount_in = true;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(SWAP_SELECTOR);
+    data.extend_from_slice(&(x_to_y as u8).to_le_bytes());
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&(by_amount_in as u8).to_le_bytes());
+    data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.state.key(), false),
+        AccountMeta::new(swap_accounts.pool.key(), false),
+        AccountMeta::new(swap_accounts.tickmap.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_x.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_y.key(), false),
+        AccountMeta::new(account_x, false),
+        AccountMeta::new(account_y, false),
+        AccountMeta::new(swap_accounts.reserve_x.key(), false),
+        AccountMeta::new(swap_accounts.reserve_y.key(), false),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.program_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_x_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_y_program.key(), false),
+    ];
+
+    let mut account_infos = vec![
+        swap_accounts.state.to_account_info(),
+        swap_accounts.pool.to_account_info(),
+        swap_accounts.tickmap.to_account_info(),
+        swap_accounts.token_x.to_account_info(),
+        swap_accounts.token_y.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.reserve_x.to_account_info(),
+        swap_accounts.reserve_y.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.program_authority.to_account_info(),
+        swap_accounts.token_x_program.to_account_info(),
+        swap_accounts.token_y_program.to_account_info(),
+    ];
+
+    for tick in swap_accounts.ticks.iter() {
+        accounts.push(AccountMeta::new(tick.key(), false));
+        account_infos.push(tick.to_account_info());
+    }
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &InvariantProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        accounts_len,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_swap_instruction() {
+        let amount_in = 100u64;
+        let x_to_y = true;
+        let by_amount_in = true;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(SWAP_SELECTOR);
+        data.extend_from_slice(&(x_to_y as u8).to_le_bytes());
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&(by_amount_in as u8).to_le_bytes());
+        data.extend_from_slice(&MIN_SQRT_PRICE.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
-use crate::{lifinity_v1pool_program, lifinity_v2pool_program, HopAccounts, SWAP_SELECTOR};
+use crate::{
+    lifinity_v1pool_program, lifinity_v2pool_program, HopAccounts, OracleParams, SWAP_SELECTOR,
+};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount};
@@ -9,6 +11,13 @@ use arrayref::array_ref;
 use super::common::DexProcessor;
 
 const ARGS_LEN: usize = 24;
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_PUB_SLOT_OFFSET: usize = 232;
+// Pyth pull oracle PriceUpdateV2 account
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const PRICE_UPDATE_V2_VERIFICATION_LEVEL_OFFSET: usize = 40; // after discriminator and write_authority
+const PRICE_MESSAGE_LEN: usize = 84;
+const ORACLE_MAIN_ACCOUNT_INDEX: usize = 10;
 
 pub struct LifinityProcessor;
 impl DexProcessor for LifinityProcessor {}
@@ -123,6 +132,70 @@ impl<'info> LifinitySwapAccountsV2<'info> {
     }
 }
 
+// Publish slot of a Pyth push oracle price account or posted slot of a Pyth pull oracle
+// PriceUpdateV2 account, None for other oracle formats
+fn get_oracle_pub_slot(data: &[u8]) -> Option<u64> {
+    if data.len() >= PYTH_PUB_SLOT_OFFSET + 8
+        && u32::from_le_bytes(*array_ref![data, 0, 4]) == PYTH_MAGIC
+    {
+        return Some(u64::from_le_bytes(*array_ref![
+            data,
+            PYTH_PUB_SLOT_OFFSET,
+            8
+        ]));
+    }
+
+    if data.len() > PRICE_UPDATE_V2_VERIFICATION_LEVEL_OFFSET
+        && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR
+    {
+        // verification_level is Partial { num_signatures: u8 } or Full
+        let price_message_offset = match data[PRICE_UPDATE_V2_VERIFICATION_LEVEL_OFFSET] {
+            0 => PRICE_UPDATE_V2_VERIFICATION_LEVEL_OFFSET + 2,
+            1 => PRICE_UPDATE_V2_VERIFICATION_LEVEL_OFFSET + 1,
+            _ => return None,
+        };
+        let posted_slot_offset = price_message_offset + PRICE_MESSAGE_LEN;
+        if data.len() >= posted_slot_offset + 8 {
+            return Some(u64::from_le_bytes(*array_ref![data, posted_slot_offset, 8]));
+        }
+    }
+    None
+}
+
+// An oracle of unknown format is not gated, the pool checks it in the swap
+fn is_oracle_main_account_stale(oracle: &AccountInfo, params: &OracleParams) -> Result<bool> {
+    if params.max_staleness_slots == 0 {
+        return Ok(false);
+    }
+    let pub_slot = get_oracle_pub_slot(&oracle.try_borrow_data()?);
+    let stale = match pub_slot {
+        Some(pub_slot) => {
+            Clock::get()?.slot.saturating_sub(pub_slot) > params.max_staleness_slots
+        }
+        None => {
+            msg!("Unknown oracle format: {}", oracle.key());
+            false
+        }
+    };
+    Ok(stale)
+}
+
+// Used by the router to skip optional V2 legs before the hop is split
+pub fn is_oracle_stale(
+    remaining_accounts: &[AccountInfo],
+    offset: usize,
+    params: &OracleParams,
+) -> Result<bool> {
+    require!(
+        remaining_accounts.len() >= offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+    is_oracle_main_account_stale(
+        &remaining_accounts[offset + ORACLE_MAIN_ACCOUNT_INDEX],
+        params,
+    )
+}
+
 pub fn swap_v1<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -223,6 +296,7 @@ pub fn swap_v2<'a>(
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
+    params: OracleParams,
 ) -> Result<u64> {
     msg!(
         "Dex::LifinitySwapV2 amount_in: {}, offset: {}",
@@ -237,6 +311,11 @@ pub fn swap_v2<'a>(
     if swap_accounts.dex_program_id.key != &lifinity_v2pool_program::id() {
         return Err(ErrorCode::InvalidProgramId.into());
     }
+    // fail before the CPI, optional legs are already skipped by the router when stale
+    require!(
+        !is_oracle_main_account_stale(swap_accounts.oracle_main_account, &params)?,
+        ErrorCode::OracleStale
+    );
 
     // check hop accounts & swap authority
     let swap_source_token = swap_accounts.swap_source_token.key();
@@ -324,4 +403,31 @@ mod tests {
         msg!("data.len: {}", data.len());
         assert!(data.len() == ARGS_LEN);
     }
+
+    #[test]
+    pub fn test_get_oracle_pub_slot() {
+        let slot = 300_000_000u64;
+
+        let mut pyth = vec![0u8; 240];
+        pyth[..4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        pyth[PYTH_PUB_SLOT_OFFSET..PYTH_PUB_SLOT_OFFSET + 8].copy_from_slice(&slot.to_le_bytes());
+        assert_eq!(get_oracle_pub_slot(&pyth), Some(slot));
+
+        // Full verification
+        let mut price_update = vec![0u8; 133];
+        price_update[..8].copy_from_slice(&PRICE_UPDATE_V2_DISCRIMINATOR);
+        price_update[40] = 1;
+        price_update[125..133].copy_from_slice(&slot.to_le_bytes());
+        assert_eq!(get_oracle_pub_slot(&price_update), Some(slot));
+
+        // Partial verification
+        let mut price_update = vec![0u8; 134];
+        price_update[..8].copy_from_slice(&PRICE_UPDATE_V2_DISCRIMINATOR);
+        price_update[41] = 3;
+        price_update[126..134].copy_from_slice(&slot.to_le_bytes());
+        assert_eq!(get_oracle_pub_slot(&price_update), Some(slot));
+
+        assert_eq!(get_oracle_pub_slot(&[7u8; 240]), None);
+        assert_eq!(get_oracle_pub_slot(&price_update[..100]), None);
+    }
 }
@@ -0,0 +1,190 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{manifest_program, HopAccounts};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::TokenAccount;
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 19;
+const SWAP_DISCRIMINATOR: u8 = 4;
+
+pub struct ManifestProcessor;
+impl DexProcessor for ManifestProcessor {}
+
+pub struct ManifestAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub market: &'info AccountInfo<'info>,
+    pub system_program: &'info AccountInfo<'info>,
+    pub base_vault: InterfaceAccount<'info, TokenAccount>,
+    pub quote_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program_base: &'info AccountInfo<'info>,
+    pub base_mint: &'info AccountInfo<'info>,
+    pub token_program_quote: &'info AccountInfo<'info>,
+    pub quote_mint: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_LEN: usize = 12;
+
+impl<'info> ManifestAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            market,
+            system_program,
+            base_vault,
+            quote_vault,
+            token_program_base,
+            base_mint,
+            token_program_quote,
+            quote_mint,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            market,
+            system_program,
+            base_vault: InterfaceAccount::try_from(base_vault)?,
+            quote_vault: InterfaceAccount::try_from(quote_vault)?,
+            token_program_base,
+            base_mint,
+            token_program_quote,
+            quote_mint,
+        })
+    }
+}
+
+pub fn swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!("Dex::Manifest amount_in: {}, offset: {}", amount_in, offset);
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = ManifestAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &manifest_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    let (is_base_in, trader_base, trader_quote) = if swap_accounts.swap_source_token.mint
+        == swap_accounts.base_vault.mint
+        && swap_accounts.swap_destination_token.mint == swap_accounts.quote_vault.mint
+    {
+        (true, swap_source_token, swap_destination_token)
+    } else if swap_accounts.swap_source_token.mint == swap_accounts.quote_vault.mint
+        && swap_accounts.swap_destination_token.mint == swap_accounts.base_vault.mint
+    {
+        (false, swap_destination_token, swap_source_token)
+    } else {
+        return Err(ErrorCode::InvalidTokenMint.into());
+    };
+
+    // Swap is an immediate-or-cancel taker order against the book
+    let is_exact_in = true;
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.push(SWAP_DISCRIMINATOR);
+    data.extend_from_slice(&amount_in.to_le_bytes()); // in_atoms
+    data.extend_from_slice(&1u64.to_le_bytes()); // out_atoms
+    data.push(is_base_in as u8);
+    data.push(is_exact_in as u8);
+
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true), // payer
+        AccountMeta::new(swap_accounts.market.key(), false),
+        AccountMeta::new_readonly(swap_accounts.system_program.key(), false),
+        AccountMeta::new(trader_base, false),
+        AccountMeta::new(trader_quote, false),
+        AccountMeta::new(swap_accounts.base_vault.key(), false),
+        AccountMeta::new(swap_accounts.quote_vault.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program_base.key(), false),
+        AccountMeta::new_readonly(swap_accounts.base_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program_quote.key(), false),
+        AccountMeta::new_readonly(swap_accounts.quote_mint.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.market.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.base_vault.to_account_info(),
+        swap_accounts.quote_vault.to_account_info(),
+        swap_accounts.token_program_base.to_account_info(),
+        swap_accounts.base_mint.to_account_info(),
+        swap_accounts.token_program_quote.to_account_info(),
+        swap_accounts.quote_mint.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &ManifestProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_swap_instruction() {
+        let amount_in = 100u64;
+        let is_base_in = true;
+        let is_exact_in = true;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.push(SWAP_DISCRIMINATOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.push(is_base_in as u8);
+        data.push(is_exact_in as u8);
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
@@ -0,0 +1,368 @@
+use crate::adapters::common::{before_check, invoke_process, SolInProcessor, SolOutProcessor};
+use crate::error::ErrorCode;
+use crate::utils::unwrap_wsol_to_authority;
+use crate::{
+    marinade_program, wsol_program, HopAccounts, MARINADE_DEPOSIT_SELECTOR,
+    MARINADE_LIQUID_UNSTAKE_SELECTOR,
+};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use arrayref::array_ref;
+
+const ARGS_LEN: usize = 16;
+
+pub struct MarinadeDepositAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub state: &'info AccountInfo<'info>,
+    pub msol_mint: InterfaceAccount<'info, Mint>,
+    pub liq_pool_sol_leg_pda: &'info AccountInfo<'info>,
+    pub liq_pool_msol_leg: &'info AccountInfo<'info>,
+    pub liq_pool_msol_leg_authority: &'info AccountInfo<'info>,
+    pub reserve_pda: &'info AccountInfo<'info>,
+    pub msol_mint_authority: &'info AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub payer: &'info AccountInfo<'info>,
+    pub temp_wsol_account: &'info AccountInfo<'info>,
+    pub wsol_mint: &'info AccountInfo<'info>,
+}
+pub const DEPOSIT_ACCOUNTS_LEN: usize = 16;
+
+pub struct MarinadeLiquidUnstakeAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub state: &'info AccountInfo<'info>,
+    pub msol_mint: InterfaceAccount<'info, Mint>,
+    pub liq_pool_sol_leg_pda: &'info AccountInfo<'info>,
+    pub liq_pool_msol_leg: &'info AccountInfo<'info>,
+    pub treasury_msol_account: &'info AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+pub const LIQUID_UNSTAKE_ACCOUNTS_LEN: usize = 11;
+
+impl<'info> MarinadeDepositAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            state,
+            msol_mint,
+            liq_pool_sol_leg_pda,
+            liq_pool_msol_leg,
+            liq_pool_msol_leg_authority,
+            reserve_pda,
+            msol_mint_authority,
+            system_program,
+            token_program,
+            payer,
+            temp_wsol_account,
+            wsol_mint,
+        ]: &[AccountInfo<'info>; DEPOSIT_ACCOUNTS_LEN] =
+            array_ref![accounts, offset, DEPOSIT_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            state,
+            msol_mint: InterfaceAccount::try_from(msol_mint)?,
+            liq_pool_sol_leg_pda,
+            liq_pool_msol_leg,
+            liq_pool_msol_leg_authority,
+            reserve_pda,
+            msol_mint_authority,
+            system_program: Program::try_from(system_program)?,
+            token_program: Program::try_from(token_program)?,
+            payer,
+            temp_wsol_account,
+            wsol_mint,
+        })
+    }
+}
+
+impl<'info> MarinadeLiquidUnstakeAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            state,
+            msol_mint,
+            liq_pool_sol_leg_pda,
+            liq_pool_msol_leg,
+            treasury_msol_account,
+            system_program,
+            token_program,
+        ]: &[AccountInfo<'info>; LIQUID_UNSTAKE_ACCOUNTS_LEN] =
+            array_ref![accounts, offset, LIQUID_UNSTAKE_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            state,
+            msol_mint: InterfaceAccount::try_from(msol_mint)?,
+            liq_pool_sol_leg_pda,
+            liq_pool_msol_leg,
+            treasury_msol_account,
+            system_program: Program::try_from(system_program)?,
+            token_program: Program::try_from(token_program)?,
+        })
+    }
+}
+
+pub fn deposit<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::MarinadeDeposit amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + DEPOSIT_ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = MarinadeDepositAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &marinade_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == wsol_program::id()
+            && swap_accounts.wsol_mint.key() == wsol_program::id()
+            && swap_accounts.swap_destination_token.mint == swap_accounts.msol_mint.key(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    // Unwrap amount_in wSOL into the swap authority, which funds the deposit
+    let authority_lamports = swap_accounts.swap_authority_pubkey.lamports();
+    let rent_paid = unwrap_wsol_to_authority(
+        swap_accounts.payer.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.temp_wsol_account.to_account_info(),
+        swap_accounts.wsol_mint.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        amount_in,
+    )?;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(MARINADE_DEPOSIT_SELECTOR);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.state.key(), false),
+        AccountMeta::new(swap_accounts.msol_mint.key(), false),
+        AccountMeta::new(swap_accounts.liq_pool_sol_leg_pda.key(), false),
+        AccountMeta::new(swap_accounts.liq_pool_msol_leg.key(), false),
+        AccountMeta::new_readonly(swap_accounts.liq_pool_msol_leg_authority.key(), false),
+        AccountMeta::new(swap_accounts.reserve_pda.key(), false),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new_readonly(swap_accounts.msol_mint_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.system_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.state.to_account_info(),
+        swap_accounts.msol_mint.to_account_info(),
+        swap_accounts.liq_pool_sol_leg_pda.to_account_info(),
+        swap_accounts.liq_pool_msol_leg.to_account_info(),
+        swap_accounts.liq_pool_msol_leg_authority.to_account_info(),
+        swap_accounts.reserve_pda.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.msol_mint_authority.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SolInProcessor {
+        authority: swap_accounts.swap_authority_pubkey.to_account_info(),
+        source_token_account: swap_accounts.swap_source_token.to_account_info(),
+        token_program: swap_accounts.token_program.to_account_info(),
+        payer: swap_accounts.payer.to_account_info(),
+        authority_lamports,
+        rent_paid,
+    };
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        DEPOSIT_ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+pub fn liquid_unstake<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::MarinadeLiquidUnstake amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + LIQUID_UNSTAKE_ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts =
+        MarinadeLiquidUnstakeAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &marinade_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.msol_mint.key()
+            && swap_accounts.swap_destination_token.mint == wsol_program::id(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(MARINADE_LIQUID_UNSTAKE_SELECTOR);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+
+    // lamports are paid to the swap authority and wrapped into the destination after the invoke
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.state.key(), false),
+        AccountMeta::new(swap_accounts.msol_mint.key(), false),
+        AccountMeta::new(swap_accounts.liq_pool_sol_leg_pda.key(), false),
+        AccountMeta::new(swap_accounts.liq_pool_msol_leg.key(), false),
+        AccountMeta::new(swap_accounts.treasury_msol_account.key(), false),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.system_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.state.to_account_info(),
+        swap_accounts.msol_mint.to_account_info(),
+        swap_accounts.liq_pool_sol_leg_pda.to_account_info(),
+        swap_accounts.liq_pool_msol_leg.to_account_info(),
+        swap_accounts.treasury_msol_account.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SolOutProcessor {
+        authority: swap_accounts.swap_authority_pubkey.to_account_info(),
+        destination_token_account: swap_accounts.swap_destination_token.to_account_info(),
+        token_program: swap_accounts.token_program.to_account_info(),
+        authority_lamports: swap_accounts.swap_authority_pubkey.lamports(),
+    };
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        LIQUID_UNSTAKE_ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_deposit_instruction() {
+        let amount_in = 100u64;
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(MARINADE_DEPOSIT_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+
+    #[test]
+    pub fn test_pack_liquid_unstake_instruction() {
+        let amount_in = 100u64;
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(MARINADE_LIQUID_UNSTAKE_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
@@ -1,16 +1,26 @@
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
 use crate::{
-    meteora_dlmm_program, meteora_dynamicpool_program, HopAccounts, SWAP_SELECTOR, ZERO_ADDRESS,
+    meteora_damm_v2_program, meteora_dlmm_program, meteora_dynamicpool_program, HopAccounts,
+    TransferHookParams, SWAP2_SELECTOR, SWAP_SELECTOR, ZERO_ADDRESS,
 };
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_hook, StateWithExtensions},
+};
 use anchor_spl::token_interface::{Mint, TokenAccount};
 use arrayref::array_ref;
 
 use super::common::DexProcessor;
 
 const ARGS_LEN: usize = 24;
+const DLMM_SWAP2_ARGS_LEN: usize = 32;
+
+// RemainingAccountsInfo slice types of DLMM
+const ACCOUNTS_TYPE_TRANSFER_HOOK_X: u8 = 0;
+const ACCOUNTS_TYPE_TRANSFER_HOOK_Y: u8 = 1;
 
 pub struct MeteoraDynamicPoolProcessor;
 impl DexProcessor for MeteoraDynamicPoolProcessor {}
@@ -59,6 +69,48 @@ pub struct MeteoraDlmmAccounts<'info> {
 }
 pub const DLMM_ACCOUNTS_LEN: usize = 18;
 
+pub struct MeteoraDlmmSwap2Accounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub lb_pair: &'info AccountInfo<'info>,
+    pub bin_array_bitmap_extension: &'info AccountInfo<'info>,
+    pub reserve_x: InterfaceAccount<'info, TokenAccount>,
+    pub reserve_y: InterfaceAccount<'info, TokenAccount>,
+    pub token_x_mint: InterfaceAccount<'info, Mint>,
+    pub token_y_mint: InterfaceAccount<'info, Mint>,
+    pub oracle: &'info AccountInfo<'info>,
+    pub host_fee_in: &'info AccountInfo<'info>,
+    pub token_x_program: &'info AccountInfo<'info>,
+    pub token_y_program: &'info AccountInfo<'info>,
+    pub memo_program: &'info AccountInfo<'info>,
+    pub event_authority: &'info AccountInfo<'info>,
+}
+// Followed by the transfer hook accounts and at least one bin array
+pub const DLMM_SWAP2_ACCOUNTS_LEN: usize = 16;
+pub const MAX_DLMM_SWAP2_ACCOUNTS_LEN: usize = 40;
+
+pub struct MeteoraDammV2Accounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub pool_authority: &'info AccountInfo<'info>,
+    pub pool: &'info AccountInfo<'info>,
+    pub token_a_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_b_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_a_mint: InterfaceAccount<'info, Mint>,
+    pub token_b_mint: InterfaceAccount<'info, Mint>,
+    pub token_a_program: &'info AccountInfo<'info>,
+    pub token_b_program: &'info AccountInfo<'info>,
+    pub referral_token_account: &'info AccountInfo<'info>,
+    pub event_authority: &'info AccountInfo<'info>,
+}
+pub const DAMM_V2_ACCOUNTS_LEN: usize = 14;
+
 impl<'info> MeteoraDynamicPoolAccounts<'info> {
     fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
         let [
@@ -145,6 +197,84 @@ impl<'info> MeteoraDlmmAccounts<'info> {
     }
 }
 
+impl<'info> MeteoraDlmmSwap2Accounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            lb_pair,
+            bin_array_bitmap_extension,
+            reserve_x,
+            reserve_y,
+            token_x_mint,
+            token_y_mint,
+            oracle,
+            host_fee_in,
+            token_x_program,
+            token_y_program,
+            memo_program,
+            event_authority,
+        ]: & [AccountInfo<'info>; DLMM_SWAP2_ACCOUNTS_LEN] = array_ref![accounts, offset, DLMM_SWAP2_ACCOUNTS_LEN];
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            lb_pair,
+            bin_array_bitmap_extension,
+            reserve_x: InterfaceAccount::try_from(reserve_x)?,
+            reserve_y: InterfaceAccount::try_from(reserve_y)?,
+            token_x_mint: InterfaceAccount::try_from(token_x_mint)?,
+            token_y_mint: InterfaceAccount::try_from(token_y_mint)?,
+            oracle,
+            host_fee_in,
+            token_x_program,
+            token_y_program,
+            memo_program,
+            event_authority,
+        })
+    }
+}
+
+impl<'info> MeteoraDammV2Accounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            pool_authority,
+            pool,
+            token_a_vault,
+            token_b_vault,
+            token_a_mint,
+            token_b_mint,
+            token_a_program,
+            token_b_program,
+            referral_token_account,
+            event_authority,
+        ]: & [AccountInfo<'info>; DAMM_V2_ACCOUNTS_LEN] = array_ref![accounts, offset, DAMM_V2_ACCOUNTS_LEN];
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            pool_authority,
+            pool,
+            token_a_vault: InterfaceAccount::try_from(token_a_vault)?,
+            token_b_vault: InterfaceAccount::try_from(token_b_vault)?,
+            token_a_mint: InterfaceAccount::try_from(token_a_mint)?,
+            token_b_mint: InterfaceAccount::try_from(token_b_mint)?,
+            token_a_program,
+            token_b_program,
+            referral_token_account,
+            event_authority,
+        })
+    }
+}
+
 pub fn swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -225,7 +355,7 @@ pub fn swap<'a>(
         program_id: swap_accounts.dex_program_id.key(),
         accounts,
         data,
-    };  
+    };
 
     let dex_processor = &MeteoraDynamicPoolProcessor;
     let amount_out = invoke_process(
@@ -353,6 +483,298 @@ pub fn swap_dlmm<'a>(
     Ok(amount_out)
 }
 
+// Transfer hook program of a Token-2022 mint, None for mints without the extension
+fn get_transfer_hook_program_id(mint: &InterfaceAccount<Mint>) -> Result<Option<Pubkey>> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::id() {
+        return Ok(None);
+    }
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(transfer_hook::get_program_id(&mint_state))
+}
+
+// RemainingAccountsInfo slices of the transfer hook accounts, X before Y, sized by the route
+fn get_transfer_hook_slices(
+    params: &TransferHookParams,
+    has_hook_x: bool,
+    has_hook_y: bool,
+    hook_accounts_len: usize,
+) -> Result<Vec<(u8, usize)>> {
+    let hook_x_len = params.hook_x_len as usize;
+    let hook_y_len = params.hook_y_len as usize;
+    // CHECK: each mint with a transfer hook has its own slice
+    require!(
+        (hook_x_len > 0) == has_hook_x
+            && (hook_y_len > 0) == has_hook_y
+            && hook_x_len + hook_y_len == hook_accounts_len,
+        ErrorCode::InvalidAccountsLength
+    );
+    let slices = [
+        (ACCOUNTS_TYPE_TRANSFER_HOOK_X, hook_x_len),
+        (ACCOUNTS_TYPE_TRANSFER_HOOK_Y, hook_y_len),
+    ]
+    .into_iter()
+    .filter(|(_, len)| *len > 0)
+    .collect();
+    Ok(slices)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn swap_dlmm_swap2<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    accounts_len: usize,
+    params: TransferHookParams,
+) -> Result<u64> {
+    msg!(
+        "Dex::MeteoraDlmmSwap2 amount_in: {}, offset: {}, accounts_len: {}",
+        amount_in,
+        offset,
+        accounts_len
+    );
+    require!(
+        accounts_len > DLMM_SWAP2_ACCOUNTS_LEN
+            && remaining_accounts.len() >= *offset + accounts_len,
+        ErrorCode::InvalidAccountsLength
+    );
+    let mut swap_accounts = MeteoraDlmmSwap2Accounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &meteora_dlmm_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    // Bin arrays are owned by the DLMM program, the rest are transfer hook accounts
+    let extra_accounts =
+        &remaining_accounts[*offset + DLMM_SWAP2_ACCOUNTS_LEN..*offset + accounts_len];
+    let (bin_arrays, hook_accounts): (Vec<&AccountInfo>, Vec<&AccountInfo>) = extra_accounts
+        .iter()
+        .partition(|account| *account.owner == meteora_dlmm_program::id());
+    require!(!bin_arrays.is_empty(), ErrorCode::InvalidAccountsLength);
+    let hook_accounts: Vec<AccountInfo> = hook_accounts.into_iter().cloned().collect();
+    let slices = get_transfer_hook_slices(
+        &params,
+        get_transfer_hook_program_id(&swap_accounts.token_x_mint)?.is_some(),
+        get_transfer_hook_program_id(&swap_accounts.token_y_mint)?.is_some(),
+        hook_accounts.len(),
+    )?;
+
+    let mut data = Vec::with_capacity(DLMM_SWAP2_ARGS_LEN);
+    data.extend_from_slice(SWAP2_SELECTOR);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&(slices.len() as u32).to_le_bytes());
+    for (accounts_type, len) in slices.iter() {
+        data.extend_from_slice(&accounts_type.to_le_bytes());
+        data.extend_from_slice(&(*len as u8).to_le_bytes());
+    }
+
+    let mut accounts = vec![
+        AccountMeta::new(swap_accounts.lb_pair.key(), false),
+        AccountMeta::new_readonly(swap_accounts.bin_array_bitmap_extension.key(), false),
+        AccountMeta::new(swap_accounts.reserve_x.key(), false),
+        AccountMeta::new(swap_accounts.reserve_y.key(), false),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new_readonly(swap_accounts.token_x_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_y_mint.key(), false),
+        AccountMeta::new(swap_accounts.oracle.key(), false),
+        AccountMeta::new(swap_accounts.host_fee_in.key(), false),
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.token_x_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_y_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.memo_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.event_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.dex_program_id.key(), false),
+    ];
+
+    let mut account_infos = vec![
+        swap_accounts.lb_pair.to_account_info(),
+        swap_accounts.bin_array_bitmap_extension.to_account_info(),
+        swap_accounts.reserve_x.to_account_info(),
+        swap_accounts.reserve_y.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.token_x_mint.to_account_info(),
+        swap_accounts.token_y_mint.to_account_info(),
+        swap_accounts.oracle.to_account_info(),
+        swap_accounts.host_fee_in.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.token_x_program.to_account_info(),
+        swap_accounts.token_y_program.to_account_info(),
+        swap_accounts.memo_program.to_account_info(),
+        swap_accounts.event_authority.to_account_info(),
+        swap_accounts.dex_program_id.to_account_info(),
+    ];
+
+    for hook_account in hook_accounts.iter() {
+        if hook_account.is_writable {
+            accounts.push(AccountMeta::new(hook_account.key(), false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(hook_account.key(), false));
+        }
+        account_infos.push(hook_account.clone());
+    }
+    for bin_array in bin_arrays.into_iter() {
+        accounts.push(AccountMeta::new(bin_array.key(), false));
+        account_infos.push(bin_array.clone());
+    }
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &MeteoraDynamicPoolProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        accounts_len,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+pub fn swap_damm_v2<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::MeteoraDammV2 amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + DAMM_V2_ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+    let mut swap_accounts = MeteoraDammV2Accounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &meteora_damm_v2_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    // check swap direction: a_to_b or b_to_a
+    let source_mint = swap_accounts.swap_source_token.mint;
+    let destination_mint = swap_accounts.swap_destination_token.mint;
+    let token_a_mint = swap_accounts.token_a_vault.mint;
+    let token_b_mint = swap_accounts.token_b_vault.mint;
+    require!(
+        (source_mint == token_a_mint && destination_mint == token_b_mint)
+            || (source_mint == token_b_mint && destination_mint == token_a_mint),
+        ErrorCode::InvalidTokenMint
+    );
+    require!(
+        swap_accounts.token_a_mint.key() == token_a_mint
+            && swap_accounts.token_b_mint.key() == token_b_mint,
+        ErrorCode::InvalidTokenMint
+    );
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(SWAP_SELECTOR);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+
+    // referral_token_account is optional, the program id stands for None
+    let referral_token_account = if swap_accounts.referral_token_account.key() == ZERO_ADDRESS {
+        swap_accounts.dex_program_id
+    } else {
+        swap_accounts.referral_token_account
+    };
+
+    let accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.pool_authority.key(), false),
+        AccountMeta::new(swap_accounts.pool.key(), false),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_accounts.token_a_vault.key(), false),
+        AccountMeta::new(swap_accounts.token_b_vault.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_a_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_b_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.token_a_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_b_program.key(), false),
+        AccountMeta::new(referral_token_account.key(), false),
+        AccountMeta::new_readonly(swap_accounts.event_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.dex_program_id.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.pool_authority.to_account_info(),
+        swap_accounts.pool.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.token_a_vault.to_account_info(),
+        swap_accounts.token_b_vault.to_account_info(),
+        swap_accounts.token_a_mint.to_account_info(),
+        swap_accounts.token_b_mint.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.token_a_program.to_account_info(),
+        swap_accounts.token_b_program.to_account_info(),
+        referral_token_account.to_account_info(),
+        swap_accounts.event_authority.to_account_info(),
+        swap_accounts.dex_program_id.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &MeteoraDynamicPoolProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        DAMM_V2_ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -368,4 +790,76 @@ mod tests {
         msg!("data.len: {}", data.len());
         assert!(data.len() == ARGS_LEN);
     }
+
+    #[test]
+    pub fn test_pack_damm_v2_swap_instruction() {
+        let amount_in = 100u64;
+        let minimum_amount_out = 1u64;
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(SWAP_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+        assert_eq!(&data[..8], SWAP_SELECTOR);
+        assert_eq!(u64::from_le_bytes(*array_ref![data, 8, 8]), amount_in);
+        assert_eq!(u64::from_le_bytes(*array_ref![data, 16, 8]), minimum_amount_out);
+    }
+
+    #[test]
+    pub fn test_pack_dlmm_swap2_instruction() {
+        let amount_in = 100u64;
+        let slices = [
+            (ACCOUNTS_TYPE_TRANSFER_HOOK_X, 3u8),
+            (ACCOUNTS_TYPE_TRANSFER_HOOK_Y, 2u8),
+        ];
+        let mut data = Vec::with_capacity(DLMM_SWAP2_ARGS_LEN);
+        data.extend_from_slice(SWAP2_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&(slices.len() as u32).to_le_bytes());
+        for (accounts_type, len) in slices.iter() {
+            data.extend_from_slice(&accounts_type.to_le_bytes());
+            data.extend_from_slice(&len.to_le_bytes());
+        }
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == DLMM_SWAP2_ARGS_LEN);
+    }
+
+    #[test]
+    pub fn test_get_transfer_hook_slices() {
+        let params = TransferHookParams {
+            hook_x_len: 3,
+            hook_y_len: 2,
+        };
+        assert_eq!(
+            get_transfer_hook_slices(&params, true, true, 5).unwrap(),
+            vec![
+                (ACCOUNTS_TYPE_TRANSFER_HOOK_X, 3),
+                (ACCOUNTS_TYPE_TRANSFER_HOOK_Y, 2)
+            ]
+        );
+
+        // only token Y has a hook
+        let params = TransferHookParams {
+            hook_x_len: 0,
+            hook_y_len: 4,
+        };
+        assert_eq!(
+            get_transfer_hook_slices(&params, false, true, 4).unwrap(),
+            vec![(ACCOUNTS_TYPE_TRANSFER_HOOK_Y, 4)]
+        );
+        assert!(
+            get_transfer_hook_slices(&TransferHookParams::default(), false, false, 0)
+                .unwrap()
+                .is_empty()
+        );
+
+        // the slices must cover the hook accounts and match the mints' hooks
+        assert!(get_transfer_hook_slices(&params, false, true, 5).is_err());
+        assert!(get_transfer_hook_slices(&params, true, true, 4).is_err());
+        assert!(get_transfer_hook_slices(&TransferHookParams::default(), true, false, 2).is_err());
+    }
 }
@@ -1,14 +1,28 @@
 pub mod aldrin;
 pub mod common;
+pub mod crema;
 pub mod fluxbeam;
+pub mod gamma;
+pub mod invariant;
 pub mod lifinity;
+pub mod manifest;
+pub mod marinade;
 pub mod meteora;
 pub mod obric_v2;
 pub mod openbookv2;
+pub mod perena;
 pub mod phoenix;
 pub mod raydium;
+pub mod raydium_launchlab;
 pub mod sanctum;
+pub mod sanctum_router;
+pub mod saros;
+pub mod solfi;
 pub mod spl_token_swap;
+pub mod stabble;
 pub mod stable_swap;
+pub mod stake_pool;
 pub mod whirlpool;
+pub mod zerofi;
 pub mod pumpfun;
+pub mod pumpswap;
@@ -1,6 +1,8 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::{before_check, invoke_process, update_amount_in_remainder};
 use crate::error::ErrorCode;
-use crate::{openbookv2_program, HopAccounts, PLACE_TAKE_ORDER_SELECTOR, ZERO_ADDRESS};
+use crate::{
+    openbookv2_program, HopAccounts, OpenBookParams, PLACE_TAKE_ORDER_SELECTOR, ZERO_ADDRESS,
+};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::TokenAccount;
@@ -10,6 +12,8 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use super::common::DexProcessor;
 
 const ARGS_LEN: usize = 35;
+const DEFAULT_MATCH_LIMIT: u8 = 50;
+const FEES_SCALE_FACTOR: u128 = 1_000_000;
 
 #[derive(
     AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, TryFromPrimitive, IntoPrimitive,
@@ -100,6 +104,57 @@ impl<'info> PlaceTakeOrderAccounts<'info> {
         let base_lots_size = i64::from_le_bytes(*array_ref![data, 456, 8]);
         Ok((base_lots_size, quote_lots_size))
     }
+
+    fn get_taker_fee(&self) -> Result<i64> {
+        let data = &self.market.try_borrow_data()?;
+        let taker_fee = i64::from_le_bytes(*array_ref![data, 488, 8]);
+        Ok(taker_fee)
+    }
+}
+
+// Convert the Q64.64 native price into quote lots per base lot, rounding against the taker
+fn get_price_lots(
+    limit_price_x64: u128,
+    base_lot_size: i64,
+    quote_lot_size: i64,
+    side: &Side,
+) -> Result<i64> {
+    let numerator = limit_price_x64
+        .checked_mul(base_lot_size as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    let denominator = (quote_lot_size as u128)
+        .checked_shl(64)
+        .ok_or(ErrorCode::CalculationError)?;
+    let price_lots = match side {
+        Side::Bid => numerator.checked_div(denominator),
+        Side::Ask => numerator
+            .checked_add(denominator - 1)
+            .and_then(|n| n.checked_div(denominator)),
+    }
+    .ok_or(ErrorCode::CalculationError)?;
+    Ok(i64::try_from(price_lots).unwrap_or(i64::MAX).max(1))
+}
+
+// Largest amount_in a full fill leaves: below one base lot for asks, for bids below the cost of one
+// base lot at the limit price with the taker fee, plus the part below one quote lot
+fn get_amount_in_dust_limit(
+    side: &Side,
+    price_lots: i64,
+    base_lot_size: i64,
+    quote_lot_size: i64,
+    taker_fee: i64,
+) -> u64 {
+    match side {
+        Side::Ask => (base_lot_size as u64).saturating_sub(1),
+        Side::Bid => {
+            let base_lot_cost = (price_lots as u128)
+                .saturating_mul(quote_lot_size as u128)
+                .saturating_mul(FEES_SCALE_FACTOR + taker_fee.max(0) as u128)
+                .div_ceil(FEES_SCALE_FACTOR);
+            let dust_limit = base_lot_cost.saturating_add(quote_lot_size as u128 - 1);
+            u64::try_from(dust_limit).unwrap_or(u64::MAX)
+        }
+    }
 }
 
 pub fn place_take_order<'a>(
@@ -109,6 +164,7 @@ pub fn place_take_order<'a>(
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
+    params: OpenBookParams,
 ) -> Result<u64> {
     msg!(
         "Dex::OpenBookV2 amount_in: {}, offset: {}",
@@ -144,7 +200,12 @@ pub fn place_take_order<'a>(
     let max_quote_lots_including_fees;
     if swap_accounts.swap_source_token.mint == swap_accounts.market_base_vault.mint {
         side = Side::Ask;
-        price_lots = 1;
+        price_lots = match params.limit_price_x64 {
+            0 => 1,
+            limit_price_x64 => {
+                get_price_lots(limit_price_x64, base_lot_size, quote_lot_size, &side)?
+            }
+        };
         max_base_lots = i64::try_from(amount_in)
             .unwrap()
             .checked_div(base_lot_size)
@@ -154,7 +215,12 @@ pub fn place_take_order<'a>(
             .ok_or(ErrorCode::CalculationError)?;
     } else {
         side = Side::Bid;
-        price_lots = i64::MAX;
+        price_lots = match params.limit_price_x64 {
+            0 => i64::MAX,
+            limit_price_x64 => {
+                get_price_lots(limit_price_x64, base_lot_size, quote_lot_size, &side)?
+            }
+        };
         max_base_lots = i64::MAX
             .checked_div(base_lot_size)
             .ok_or(ErrorCode::CalculationError)?;
@@ -163,8 +229,18 @@ pub fn place_take_order<'a>(
             .checked_div(quote_lot_size)
             .ok_or(ErrorCode::CalculationError)?;
     }
+    let amount_in_dust_limit = get_amount_in_dust_limit(
+        &side,
+        price_lots,
+        base_lot_size,
+        quote_lot_size,
+        swap_accounts.get_taker_fee()?,
+    );
     let order_type = 3u8;
-    let limit = 50u8;
+    let limit = match params.match_limit {
+        0 => DEFAULT_MATCH_LIMIT,
+        match_limit => match_limit,
+    };
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(PLACE_TAKE_ORDER_SELECTOR);
@@ -247,6 +323,7 @@ pub fn place_take_order<'a>(
         data,
     };
 
+    let before_source_balance = swap_accounts.swap_source_token.amount;
     let dex_processor = &OpenbookV2Processor;
     let amount_out = invoke_process(
         dex_processor,
@@ -260,6 +337,15 @@ pub fn place_take_order<'a>(
         ACCOUNTS_LEN,
         proxy_swap,
     )?;
+
+    // Only the filled amount is taken from the source account
+    update_amount_in_remainder(
+        &mut swap_accounts.swap_source_token,
+        before_source_balance,
+        amount_in,
+        hop_accounts,
+    )?;
+    hop_accounts.amount_in_dust_limit = amount_in_dust_limit;
     Ok(amount_out)
 }
 
@@ -294,6 +380,20 @@ mod tests {
         //032c47031ac7cb5500ffffffffffffff7fa38d23d6e25300004ea05c02000000000332
     }
 
+    #[test]
+    pub fn test_get_price_lots() {
+        // 1 base native = 0.5 quote native, base lot 100, quote lot 10: 5 quote lots per base lot
+        let limit_price_x64 = 1u128 << 63;
+        assert_eq!(get_price_lots(limit_price_x64, 100, 10, &Side::Bid).unwrap(), 5);
+        assert_eq!(get_price_lots(limit_price_x64, 100, 10, &Side::Ask).unwrap(), 5);
+        // 5.5 quote lots per base lot rounds against the taker
+        let limit_price_x64 = (11u128 << 64) / 200;
+        assert_eq!(get_price_lots(limit_price_x64, 1000, 10, &Side::Bid).unwrap(), 5);
+        assert_eq!(get_price_lots(limit_price_x64, 1000, 10, &Side::Ask).unwrap(), 6);
+        // never below one lot
+        assert_eq!(get_price_lots(limit_price_x64, 1, 10, &Side::Bid).unwrap(), 1);
+    }
+
     #[test]
     pub fn test_div() {
         let amount_in: u64 = 113934176;
@@ -301,4 +401,42 @@ mod tests {
         let res = i64::try_from(amount_in).unwrap() / base_lot_size;
         msg!("res: {}", res);
     }
+
+    #[test]
+    pub fn test_amount_in_remainder_at_hop_1() {
+        use crate::adapters::common::check_amount_in_remainder;
+
+        let mut hop_accounts = HopAccounts {
+            last_to_account: ZERO_ADDRESS,
+            from_account: ZERO_ADDRESS,
+            to_account: ZERO_ADDRESS,
+            amount_in_remainder: 0,
+            amount_in_dust_limit: 0,
+        };
+
+        // ask: 39624782 base native fills 396 lots of 100000, the sub-lot part stays
+        hop_accounts.amount_in_dust_limit =
+            get_amount_in_dust_limit(&Side::Ask, 1, 100000, 1, 0);
+        hop_accounts.amount_in_remainder = 39624782 % 100000;
+        assert!(check_amount_in_remainder(1, &hop_accounts).is_ok());
+        // one unfilled lot can't stay in the intermediate account
+        hop_accounts.amount_in_remainder += 100000;
+        assert!(check_amount_in_remainder(1, &hop_accounts).is_err());
+        assert!(check_amount_in_remainder(0, &hop_accounts).is_ok());
+
+        // bid: 5 quote lots of 10 per base lot at the limit and a 0.04% taker fee, 51 per lot
+        hop_accounts.amount_in_dust_limit =
+            get_amount_in_dust_limit(&Side::Bid, 5, 100, 10, 400);
+        assert_eq!(hop_accounts.amount_in_dust_limit, 60);
+        hop_accounts.amount_in_remainder = 57;
+        assert!(check_amount_in_remainder(1, &hop_accounts).is_ok());
+        hop_accounts.amount_in_remainder = 61;
+        assert!(check_amount_in_remainder(1, &hop_accounts).is_err());
+
+        // bid without a limit price
+        assert_eq!(
+            get_amount_in_dust_limit(&Side::Bid, i64::MAX, 100, 10, 400),
+            u64::MAX
+        );
+    }
 }
@@ -0,0 +1,185 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{perena_program, HopAccounts, TokenIndexParams, SWAP_EXACT_IN_SELECTOR};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 26;
+
+pub struct PerenaProcessor;
+impl DexProcessor for PerenaProcessor {}
+
+pub struct PerenaAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub pool: &'info AccountInfo<'info>,
+    pub in_vault: InterfaceAccount<'info, TokenAccount>,
+    pub out_vault: InterfaceAccount<'info, TokenAccount>,
+    pub in_mint: InterfaceAccount<'info, Mint>,
+    pub out_mint: InterfaceAccount<'info, Mint>,
+    pub in_token_program: &'info AccountInfo<'info>,
+    pub out_token_program: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_LEN: usize = 11;
+
+impl<'info> PerenaAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            pool,
+            in_vault,
+            out_vault,
+            in_mint,
+            out_mint,
+            in_token_program,
+            out_token_program,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            pool,
+            in_vault: InterfaceAccount::try_from(in_vault)?,
+            out_vault: InterfaceAccount::try_from(out_vault)?,
+            in_mint: InterfaceAccount::try_from(in_mint)?,
+            out_mint: InterfaceAccount::try_from(out_mint)?,
+            in_token_program,
+            out_token_program,
+        })
+    }
+}
+
+pub fn swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    params: TokenIndexParams,
+) -> Result<u64> {
+    msg!("Dex::Perena amount_in: {}, offset: {}", amount_in, offset);
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = PerenaAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &perena_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.in_mint.key()
+            && swap_accounts.swap_destination_token.mint == swap_accounts.out_mint.key()
+            && swap_accounts.in_vault.mint == swap_accounts.in_mint.key()
+            && swap_accounts.out_vault.mint == swap_accounts.out_mint.key(),
+        ErrorCode::InvalidTokenMint
+    );
+    // the pool holds up to 8 assets, the route tells which slots are traded
+    require!(
+        params.in_index != params.out_index,
+        ErrorCode::InvalidDexParams
+    );
+    let min_amount_out = 1u64;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(SWAP_EXACT_IN_SELECTOR);
+    data.extend_from_slice(&params.in_index.to_le_bytes());
+    data.extend_from_slice(&params.out_index.to_le_bytes());
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&min_amount_out.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_accounts.pool.key(), false),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_accounts.in_vault.key(), false),
+        AccountMeta::new(swap_accounts.out_vault.key(), false),
+        AccountMeta::new_readonly(swap_accounts.in_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.out_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.in_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.out_token_program.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.pool.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.in_vault.to_account_info(),
+        swap_accounts.out_vault.to_account_info(),
+        swap_accounts.in_mint.to_account_info(),
+        swap_accounts.out_mint.to_account_info(),
+        swap_accounts.in_token_program.to_account_info(),
+        swap_accounts.out_token_program.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &PerenaProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_swap_exact_in_instruction() {
+        let in_index = 0u8;
+        let out_index = 1u8;
+        let amount_in = 100u64;
+        let min_amount_out = 1u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(SWAP_EXACT_IN_SELECTOR);
+        data.extend_from_slice(&in_index.to_le_bytes());
+        data.extend_from_slice(&out_index.to_le_bytes());
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&min_amount_out.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
@@ -1,6 +1,6 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::{before_check, invoke_process, update_amount_in_remainder};
 use crate::error::ErrorCode;
-use crate::{phoenix_program, HopAccounts};
+use crate::{phoenix_program, HopAccounts, PhoenixParams};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::TokenAccount;
@@ -10,6 +10,7 @@ use std::u64;
 use super::common::DexProcessor;
 
 const ARGS_LEN: usize = 55;
+const SLIPPAGE_DENOMINATOR: u64 = 10000;
 
 pub struct PhoenixProcessor;
 impl DexProcessor for PhoenixProcessor {}
@@ -63,6 +64,32 @@ impl<'info> SwapAccounts<'info> {
     }
 }
 
+// Min lots of the output token to fill, from the expected amount out and slippage of the route
+fn get_min_lots_to_fill(params: &PhoenixParams, output_lot_size: u64) -> Result<u64> {
+    require!(
+        params.slippage_bps as u64 <= SLIPPAGE_DENOMINATOR,
+        ErrorCode::InvalidDexParams
+    );
+    let min_amount_out = (params.expect_amount_out as u128)
+        .checked_mul((SLIPPAGE_DENOMINATOR - params.slippage_bps as u64) as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(SLIPPAGE_DENOMINATOR as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    let min_lots = min_amount_out
+        .checked_div(output_lot_size as u128)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(min_lots as u64)
+}
+
+// Largest amount_in a full fill leaves: below one base lot for asks. Bids carry no limit price, so
+// the quote left below the cost of the next base lot has no bound
+fn get_amount_in_dust_limit(side: u8, base_lot_size: u64) -> u64 {
+    match side {
+        1 => base_lot_size.saturating_sub(1),
+        _ => u64::MAX,
+    }
+}
+
 pub fn swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -70,6 +97,7 @@ pub fn swap<'a>(
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
+    params: PhoenixParams,
 ) -> Result<u64> {
     msg!("Dex::Phoenix amount_in: {}, offset: {}", amount_in, offset);
     require!(
@@ -96,7 +124,7 @@ pub fn swap<'a>(
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     let (base_lot_size, quote_lot_size) = swap_accounts.get_lot_size()?;
-    let (side, num_base_lots, num_quote_lots) =
+    let (side, num_base_lots, num_quote_lots, min_base_lots_to_fill, min_quote_lots_to_fill) =
         if swap_accounts.swap_source_token.mint == swap_accounts.base_vault.mint {
             (
                 1u8,
@@ -104,6 +132,8 @@ pub fn swap<'a>(
                     .checked_div(base_lot_size)
                     .ok_or(ErrorCode::CalculationError)?,
                 0u64,
+                0u64,
+                get_min_lots_to_fill(&params, quote_lot_size)?,
             ) // 'ask' side
         } else {
             (
@@ -112,6 +142,8 @@ pub fn swap<'a>(
                 amount_in
                     .checked_div(quote_lot_size)
                     .ok_or(ErrorCode::CalculationError)?,
+                get_min_lots_to_fill(&params, base_lot_size)?,
+                0u64,
             ) // 'bid' side
         };
 
@@ -124,8 +156,8 @@ pub fn swap<'a>(
     data.push(0); // Indicates absence of price_in_ticks (market order)
     data.extend_from_slice(&num_base_lots.to_le_bytes());
     data.extend_from_slice(&num_quote_lots.to_le_bytes());
-    data.extend_from_slice(&0u64.to_le_bytes()); // min_base_lots_to_fill
-    data.extend_from_slice(&0u64.to_le_bytes()); // min_quote_lots_to_fill
+    data.extend_from_slice(&min_base_lots_to_fill.to_le_bytes());
+    data.extend_from_slice(&min_quote_lots_to_fill.to_le_bytes());
     data.push(self_trade_behavior);
     data.push(0); // Indicates absence of match_limit
     data.extend_from_slice(&0u128.to_le_bytes()); // client_order_id
@@ -174,6 +206,7 @@ pub fn swap<'a>(
         data,
     };
 
+    let before_source_balance = swap_accounts.swap_source_token.amount;
     let dex_processor = &PhoenixProcessor;
     let amount_out = invoke_process(
         dex_processor,
@@ -187,6 +220,16 @@ pub fn swap<'a>(
         ACCOUNTS_LEN,
         proxy_swap,
     )?;
+
+    // The sub-lot part of amount_in and any unfilled lots stay in the source account, the router
+    // forwards them to the next dex or returns them to the user
+    update_amount_in_remainder(
+        &mut swap_accounts.swap_source_token,
+        before_source_balance,
+        amount_in,
+        hop_accounts,
+    )?;
+    hop_accounts.amount_in_dust_limit = get_amount_in_dust_limit(side, base_lot_size);
     Ok(amount_out)
 }
 
@@ -220,4 +263,44 @@ mod tests {
         assert!(data.len() == ARGS_LEN);
         println!("constructed_data {:?}", data);
     }
+
+    #[test]
+    pub fn test_get_min_lots_to_fill() {
+        let params = PhoenixParams {
+            expect_amount_out: 1_000_000,
+            slippage_bps: 50,
+        };
+        assert_eq!(get_min_lots_to_fill(&params, 1000).unwrap(), 995);
+        assert_eq!(get_min_lots_to_fill(&PhoenixParams::default(), 1000).unwrap(), 0);
+
+        let params = PhoenixParams {
+            expect_amount_out: 1_000_000,
+            slippage_bps: 10001,
+        };
+        assert!(get_min_lots_to_fill(&params, 1000).is_err());
+    }
+
+    #[test]
+    pub fn test_amount_in_remainder_at_hop_1() {
+        use crate::adapters::common::check_amount_in_remainder;
+        use crate::ZERO_ADDRESS;
+
+        // ask: 68378123 base native fills 68378 lots of 1000, the sub-lot part stays
+        let mut hop_accounts = HopAccounts {
+            last_to_account: ZERO_ADDRESS,
+            from_account: ZERO_ADDRESS,
+            to_account: ZERO_ADDRESS,
+            amount_in_remainder: 68378123 % 1000,
+            amount_in_dust_limit: get_amount_in_dust_limit(1, 1000),
+        };
+        assert!(check_amount_in_remainder(1, &hop_accounts).is_ok());
+        // unfilled lots can't stay in the intermediate account
+        hop_accounts.amount_in_remainder += 1000;
+        assert!(check_amount_in_remainder(1, &hop_accounts).is_err());
+        assert!(check_amount_in_remainder(0, &hop_accounts).is_ok());
+
+        // bid
+        hop_accounts.amount_in_dust_limit = get_amount_in_dust_limit(0, 1000);
+        assert!(check_amount_in_remainder(1, &hop_accounts).is_ok());
+    }
 }
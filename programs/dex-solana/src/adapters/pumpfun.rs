@@ -2,66 +2,19 @@ use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::spl_token::instruction::{close_account, sync_native};
+use anchor_spl::token::spl_token::instruction::close_account;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount};
 use arrayref::array_ref;
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::{before_check, invoke_process, update_amount_in_remainder};
 use crate::error::ErrorCode;
-use crate::utils::transfer_sol_from_user;
-use crate::{pumpfun_program, HopAccounts, PUMPFUN_BUY_SELECTOR, PUMPFUN_SELL_SELECTOR, ZERO_ADDRESS};
+use crate::utils::{transfer_sol_from_authority, unwrap_wsol_to_authority, wrap_sol_from_authority};
+use crate::{pumpfun_program, wsol_program, HopAccounts, PUMPFUN_BUY_SELECTOR, PUMPFUN_SELL_SELECTOR};
 
 use super::common::DexProcessor;
 
 const ARGS_LEN: usize = 24;
-
-pub fn pumpfun_before_check(
-    swap_authority_pubkey: &AccountInfo,
-    swap_source_token: Pubkey,
-    swap_source_token_account: TokenAccount,
-    swap_destination_token: Pubkey,
-    hop_accounts: &mut HopAccounts,
-    hop: usize,
-    proxy_swap: bool,
-) -> Result<()> {
-    // check hop accounts
-    if hop_accounts.from_account != ZERO_ADDRESS {
-        require_keys_eq!(
-            swap_source_token,
-            hop_accounts.from_account,
-            ErrorCode::InvalidHopAccounts
-        );
-        require_keys_eq!(
-            swap_destination_token,
-            hop_accounts.to_account,
-            ErrorCode::InvalidHopAccounts
-        );
-    }
-    if hop_accounts.last_to_account != ZERO_ADDRESS {
-        require_keys_eq!(
-            swap_source_token,
-            hop_accounts.last_to_account,
-            ErrorCode::InvalidHopFromAccount
-        );
-    }
-
-    // check swap authority
-    if !proxy_swap && hop == 0 {
-        require!(
-            swap_authority_pubkey.is_signer,
-            ErrorCode::SwapAuthorityIsNotSigner
-        );
-    } else {
-        require_keys_eq!(
-            swap_authority_pubkey.key(),
-            swap_source_token_account.owner,
-            ErrorCode::InvalidAuthorityPda
-        );
-    }
-    Ok(())
-}
-
-
+const FEE_DENOMINATOR: u64 = 10000;
 
 pub struct PumpfunBuyAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -79,30 +32,74 @@ pub struct PumpfunBuyAccounts<'info> {
     pub event_authority: &'info AccountInfo<'info>,
 }
 
-pub const BUY_ACCOUNTS_LEN: usize = 13;
+// Appended to the buy accounts to buy through a temporary wSOL account
+pub struct PumpfunTempWsolAccounts<'info> {
+    pub payer: &'info AccountInfo<'info>,
+    pub temp_wsol_account: &'info AccountInfo<'info>,
+    pub wsol_mint: &'info AccountInfo<'info>,
+}
 
+pub const BUY_ACCOUNTS_LEN: usize = 13;
+pub const BUY_TEMP_WSOL_ACCOUNTS_LEN: usize = 16;
 
-pub struct PumpfunBuyProcessor;
-impl DexProcessor for PumpfunBuyProcessor { 
+// Legacy layout without the temporary wSOL accounts: the source wSOL account is closed into the
+// swap authority, only for the user's own account at the first hop
+pub struct PumpfunCloseAccountBuyProcessor;
+impl DexProcessor for PumpfunCloseAccountBuyProcessor {
     fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let source_token_account = account_infos.get(12).unwrap();
         let token_program = account_infos.get(8).unwrap();
         let user = account_infos.get(6).unwrap();
 
         let close_account_ix = close_account(
-            &token_program.key(),    
+            &token_program.key(),
             &source_token_account.key(),
             &user.key(),
             &user.key(),
             &[&user.key()],
         )?;
-
-        invoke(&close_account_ix, &[source_token_account.to_account_info(), token_program.to_account_info(), user.to_account_info()])?;
-        
+        invoke(
+            &close_account_ix,
+            &[
+                source_token_account.to_account_info(),
+                token_program.to_account_info(),
+                user.to_account_info(),
+            ],
+        )?;
         Ok(0)
     }
 }
 
+pub struct PumpfunBuyProcessor {
+    pub authority_lamports: u64,
+    pub rent_paid: u64,
+}
+
+impl DexProcessor for PumpfunBuyProcessor {
+    fn after_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
+        let user = account_infos.get(6).unwrap();
+        let token_program = account_infos.get(8).unwrap();
+        let source_token_account = account_infos.get(12).unwrap();
+        let payer = account_infos.get(13).unwrap();
+
+        // Return the rent of the temporary wSOL account to payer
+        transfer_sol_from_authority(user.to_account_info(), payer.to_account_info(), self.rent_paid)?;
+
+        // Wrap the unspent lamports back into the source token account
+        let refund = user
+            .lamports()
+            .checked_sub(self.authority_lamports)
+            .ok_or(ErrorCode::CalculationError)?;
+        wrap_sol_from_authority(
+            user.to_account_info(),
+            source_token_account.to_account_info(),
+            token_program.to_account_info(),
+            refund,
+        )?;
+        Ok(refund)
+    }
+}
+
 impl<'info> PumpfunBuyAccounts<'info> {
     fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
         let [
@@ -119,7 +116,6 @@ impl<'info> PumpfunBuyAccounts<'info> {
             token_program,
             rent,
             event_authority,
-            
         ]: &[AccountInfo<'info>; BUY_ACCOUNTS_LEN] = array_ref![accounts, offset, BUY_ACCOUNTS_LEN];
 
         Ok(Self {
@@ -139,19 +135,89 @@ impl<'info> PumpfunBuyAccounts<'info> {
         })
     }
 
-    fn cal_token_amount_out(&self, amount_in: u64) -> Result<u128> {
-        let data = &self.bonding_curve.try_borrow_data()?;
-        let virtual_token_reserves = u64::from_le_bytes(*array_ref![data, 8, 8]);
-        let virtual_sol_reserves = u64::from_le_bytes(*array_ref![data, 16, 8]);
-        let numerator = virtual_token_reserves as u128 * virtual_sol_reserves as u128;
-        let denominator = virtual_sol_reserves as u128 + amount_in as u128;
-        let amount_out = (virtual_token_reserves as u128)
-            .checked_sub(numerator.checked_div(denominator).unwrap()).unwrap();
-        Ok(amount_out as u128)
-    }    
+    fn cal_token_amount_out(&self, sol_amount_in: u64) -> Result<u64> {
+        let (virtual_token_reserves, virtual_sol_reserves) =
+            get_virtual_reserves(self.bonding_curve)?;
+        get_token_amount_out(virtual_token_reserves, virtual_sol_reserves, sol_amount_in)
+    }
+}
+
+impl<'info> PumpfunTempWsolAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [payer, temp_wsol_account, wsol_mint]: &[AccountInfo<'info>; 3] =
+            array_ref![accounts, offset + BUY_ACCOUNTS_LEN, 3];
+        Ok(Self {
+            payer,
+            temp_wsol_account,
+            wsol_mint,
+        })
+    }
+}
+
+// virtual_token_reserves and virtual_sol_reserves of the bonding curve
+fn get_virtual_reserves(bonding_curve: &AccountInfo) -> Result<(u64, u64)> {
+    let data = &bonding_curve.try_borrow_data()?;
+    require!(data.len() >= 24, ErrorCode::InvalidPool);
+    let virtual_token_reserves = u64::from_le_bytes(*array_ref![data, 8, 8]);
+    let virtual_sol_reserves = u64::from_le_bytes(*array_ref![data, 16, 8]);
+    Ok((virtual_token_reserves, virtual_sol_reserves))
+}
+
+// fee_basis_points + creator_fee_basis_points of the pump.fun global account
+fn get_total_fee_basis_points(global: &AccountInfo) -> Result<u64> {
+    let data = &global.try_borrow_data()?;
+    require!(data.len() >= 113, ErrorCode::InvalidPool);
+    let fee_basis_points = u64::from_le_bytes(*array_ref![data, 105, 8]);
+    // globals created before the creator fee have no creator_fee_basis_points
+    let creator_fee_basis_points = if data.len() >= 162 {
+        u64::from_le_bytes(*array_ref![data, 154, 8])
+    } else {
+        0
+    };
+    let total_fee_basis_points = fee_basis_points
+        .checked_add(creator_fee_basis_points)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(total_fee_basis_points)
+}
+
+// token amount out for an exact sol amount in, before fees
+fn get_token_amount_out(
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    sol_amount_in: u64,
+) -> Result<u64> {
+    let amount_out = (virtual_token_reserves as u128)
+        .checked_mul(sol_amount_in as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(
+            (virtual_sol_reserves as u128)
+                .checked_add(sol_amount_in as u128)
+                .ok_or(ErrorCode::CalculationError)?,
+        )
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(amount_out as u64)
+}
+
+// sol amount out for an exact token amount in, before fees
+fn get_sol_amount_out(
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    token_amount_in: u64,
+) -> Result<u64> {
+    let amount_out = (virtual_sol_reserves as u128)
+        .checked_mul(token_amount_in as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(
+            (virtual_token_reserves as u128)
+                .checked_add(token_amount_in as u128)
+                .ok_or(ErrorCode::CalculationError)?,
+        )
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(amount_out as u64)
 }
 
 
+// accounts_len selects the temporary wSOL layout, None or BUY_ACCOUNTS_LEN for the legacy layout
 pub fn buy<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -159,14 +225,17 @@ pub fn buy<'a>(
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
+    accounts_len: Option<usize>,
 ) -> Result<u64> {
     msg!(
         "Dex::Pumpfun amount_in: {}, offset: {}",
         amount_in,
         offset
     );
+    let accounts_len = accounts_len.unwrap_or(BUY_ACCOUNTS_LEN);
     require!(
-        remaining_accounts.len() >= *offset + BUY_ACCOUNTS_LEN,
+        (accounts_len == BUY_ACCOUNTS_LEN || accounts_len == BUY_TEMP_WSOL_ACCOUNTS_LEN)
+            && remaining_accounts.len() >= *offset + accounts_len,
         ErrorCode::InvalidAccountsLength
     );
 
@@ -174,19 +243,36 @@ pub fn buy<'a>(
     if swap_accounts.dex_program_id.key != &pumpfun_program::id() {
         return Err(ErrorCode::InvalidProgramId.into());
     }
+    let temp_wsol_accounts = if accounts_len == BUY_TEMP_WSOL_ACCOUNTS_LEN {
+        Some(PumpfunTempWsolAccounts::parse_accounts(remaining_accounts, *offset)?)
+    } else {
+        // CHECK: closing the source account only works for the user's own account
+        require!(!proxy_swap && hop == 0, ErrorCode::InvalidAccountsLength);
+        None
+    };
 
-    pumpfun_before_check(
+    before_check(
         swap_accounts.swap_authority_pubkey,
         swap_accounts.swap_source_token.key(),
-        *swap_accounts.swap_source_token,
         swap_accounts.swap_destination_token.key(),
         hop_accounts,
         hop,
         proxy_swap,
     )?;
+    require!(
+        swap_accounts.swap_source_token.mint == wsol_program::id(),
+        ErrorCode::InvalidTokenMint
+    );
 
-    let real_amount_in = amount_in.checked_mul(980009).unwrap().checked_div(1000000).unwrap();
-    let amount_out = swap_accounts.cal_token_amount_out(real_amount_in)? as u64;
+    // fees are charged on top of the sol amount, leave room for each fee being rounded up
+    let fee_basis_points = get_total_fee_basis_points(swap_accounts.global)?;
+    let real_amount_in = (amount_in as u128)
+        .checked_mul(FEE_DENOMINATOR as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(FEE_DENOMINATOR as u128 + fee_basis_points as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        .saturating_sub(3) as u64;
+    let amount_out = swap_accounts.cal_token_amount_out(real_amount_in)?;
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(PUMPFUN_BUY_SELECTOR); 
@@ -208,7 +294,7 @@ pub fn buy<'a>(
         AccountMeta::new_readonly(swap_accounts.dex_program_id.key(), false)
     ];
 
-    let account_infos = vec![
+    let mut account_infos = vec![
         swap_accounts.global.to_account_info(),
         swap_accounts.fee_recipient.to_account_info(),
         swap_accounts.mint.to_account_info(),
@@ -229,8 +315,47 @@ pub fn buy<'a>(
         accounts,
         data,
     };
-    
-    let dex_processor = &PumpfunBuyProcessor;
+
+    let Some(temp_wsol_accounts) = temp_wsol_accounts else {
+        // The unspent lamports stay with the user
+        let dex_processor = &PumpfunCloseAccountBuyProcessor;
+        return invoke_process(
+            dex_processor,
+            &account_infos,
+            swap_accounts.swap_source_token.key(),
+            &mut swap_accounts.swap_destination_token,
+            hop_accounts,
+            instruction,
+            hop,
+            offset,
+            BUY_ACCOUNTS_LEN,
+            proxy_swap,
+        );
+    };
+    require!(
+        temp_wsol_accounts.wsol_mint.key() == wsol_program::id(),
+        ErrorCode::InvalidTokenMint
+    );
+    account_infos.push(temp_wsol_accounts.payer.to_account_info());
+
+    // Unwrap amount_in wSOL into the swap authority through the temporary wSOL account
+    let before_source_balance = swap_accounts.swap_source_token.amount;
+    let authority_lamports = swap_accounts.swap_authority_pubkey.lamports();
+    let rent_paid = unwrap_wsol_to_authority(
+        temp_wsol_accounts.payer.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        temp_wsol_accounts.temp_wsol_account.to_account_info(),
+        temp_wsol_accounts.wsol_mint.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        amount_in,
+    )?;
+
+    let dex_processor = &PumpfunBuyProcessor {
+        authority_lamports,
+        rent_paid,
+    };
     let amount_out = invoke_process(
         dex_processor,
         &account_infos,
@@ -240,42 +365,46 @@ pub fn buy<'a>(
         instruction,
         hop,
         offset,
-        BUY_ACCOUNTS_LEN,
-        false,
+        BUY_TEMP_WSOL_ACCOUNTS_LEN,
+        proxy_swap,
     )?;
+
+    // The unspent lamports were wrapped back into the source account, at most the fees and
+    // rounding headroom kept out of the curve quote
+    update_amount_in_remainder(
+        &mut swap_accounts.swap_source_token,
+        before_source_balance,
+        amount_in,
+        hop_accounts,
+    )?;
+    hop_accounts.amount_in_dust_limit = amount_in.saturating_sub(real_amount_in);
     Ok(amount_out)
 }
 
 
 
 pub struct PumpfunSellProcessor {
-    pub amount: u64,
+    pub authority_lamports: u64,
 }
 
 impl DexProcessor for PumpfunSellProcessor {
-
-    fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
-        let destination_token_account = account_infos.last().unwrap();
-        let balance = destination_token_account.get_lamports();
-        Ok(balance)
-    }
-
     fn after_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let destination_token_account = account_infos.last().unwrap();
         let user = account_infos.get(6).unwrap();
         let token_program = account_infos.get(9).unwrap();
-        transfer_sol_from_user(
+
+        // Wrap the sol received by the swap authority into the destination token account
+        let sol_amount_out = user
+            .lamports()
+            .checked_sub(self.authority_lamports)
+            .ok_or(ErrorCode::CalculationError)?;
+        wrap_sol_from_authority(
             user.to_account_info(),
             destination_token_account.to_account_info(),
-            self.amount,
+            token_program.to_account_info(),
+            sol_amount_out,
         )?;
-        
-        let sync_native_ix = sync_native(
-            &token_program.key(),
-            &destination_token_account.key(),
-        )?;
-        invoke(&sync_native_ix, &[destination_token_account.to_account_info(), token_program.to_account_info()])?;
-        Ok(self.amount)
+        Ok(sol_amount_out)
     }
 }
 
@@ -336,14 +465,10 @@ impl<'info> PumpfunSellAccounts<'info> {
         })
     }
 
-    fn cal_sol_amount_out(&self, token_amount_in: u64) -> Result<u128> {
-        let data = &self.bonding_curve.try_borrow_data()?;
-        let virtual_token_reserves = u64::from_le_bytes(*array_ref![data, 8, 8]);
-        let virtual_sol_reserves = u64::from_le_bytes(*array_ref![data, 16, 8]);
-        let numerator = virtual_token_reserves as u128 * virtual_sol_reserves as u128;
-
-        let sol_amount_out = numerator.checked_div(virtual_token_reserves as u128 - token_amount_in as u128).unwrap() - virtual_sol_reserves as u128;
-        Ok(sol_amount_out)
+    fn cal_sol_amount_out(&self, token_amount_in: u64) -> Result<u64> {
+        let (virtual_token_reserves, virtual_sol_reserves) =
+            get_virtual_reserves(self.bonding_curve)?;
+        get_sol_amount_out(virtual_token_reserves, virtual_sol_reserves, token_amount_in)
     }
 }
 
@@ -381,8 +506,20 @@ pub fn sell<'a>(
         proxy_swap
     )?;
 
-    let sol_amount_out = swap_accounts.cal_sol_amount_out(amount_in)? as u64;
-    let min_sol_amount_out = sol_amount_out.checked_mul(980009).unwrap().checked_div(1000000).unwrap();
+    require!(
+        swap_accounts.swap_destination_token.mint == wsol_program::id(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    // fees are deducted from the sol amount, leave room for each fee being rounded up
+    let fee_basis_points = get_total_fee_basis_points(swap_accounts.global)?;
+    let sol_amount_out = swap_accounts.cal_sol_amount_out(amount_in)?;
+    let min_sol_amount_out = (sol_amount_out as u128)
+        .checked_mul(FEE_DENOMINATOR.saturating_sub(fee_basis_points) as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(FEE_DENOMINATOR as u128)
+        .ok_or(ErrorCode::CalculationError)?
+        .saturating_sub(3) as u64;
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(PUMPFUN_SELL_SELECTOR); 
@@ -426,8 +563,9 @@ pub fn sell<'a>(
         accounts,
         data,
     };
-    let dex_processor = &PumpfunSellProcessor{amount: min_sol_amount_out};
-   
+    let dex_processor = &PumpfunSellProcessor {
+        authority_lamports: swap_accounts.swap_authority_pubkey.lamports(),
+    };
     let amount_out = invoke_process(
         dex_processor,
         &account_infos,
@@ -438,7 +576,49 @@ pub fn sell<'a>(
         hop,
         offset,
         SELL_ACCOUNTS_LEN,
-        false,
+        proxy_swap,
     )?;
     Ok(amount_out)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_buy_instruction() {
+        let token_amount_out = 100u64;
+        let max_sol_cost = 1000u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(PUMPFUN_BUY_SELECTOR);
+        data.extend_from_slice(&token_amount_out.to_le_bytes());
+        data.extend_from_slice(&max_sol_cost.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+
+    #[test]
+    pub fn test_get_amount_out() {
+        let virtual_token_reserves = 1_073_000_000_000_000u64;
+        let virtual_sol_reserves = 30_000_000_000u64;
+
+        // selling the tokens bought back gives at most the sol amount in
+        let sol_amount_in = 1_000_000_000u64;
+        let token_amount_out =
+            get_token_amount_out(virtual_token_reserves, virtual_sol_reserves, sol_amount_in)
+                .unwrap();
+        let sol_amount_out = get_sol_amount_out(
+            virtual_token_reserves - token_amount_out,
+            virtual_sol_reserves + sol_amount_in,
+            token_amount_out,
+        )
+        .unwrap();
+        assert!(sol_amount_out <= sol_amount_in);
+        assert!(sol_amount_in - sol_amount_out <= 1);
+
+        // more tokens than the virtual reserves don't overflow
+        assert!(get_sol_amount_out(1, virtual_sol_reserves, u64::MAX).unwrap() < virtual_sol_reserves);
+    }
+}
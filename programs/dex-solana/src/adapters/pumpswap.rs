@@ -0,0 +1,384 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{pumpswap_program, HopAccounts, PUMPFUN_BUY_SELECTOR, PUMPFUN_SELL_SELECTOR};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 24;
+const FEE_DENOMINATOR: u128 = 10000;
+
+pub struct PumpSwapProcessor;
+impl DexProcessor for PumpSwapProcessor {}
+
+pub struct PumpSwapAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub pool: &'info AccountInfo<'info>,
+    pub global_config: &'info AccountInfo<'info>,
+    pub base_mint: InterfaceAccount<'info, Mint>,
+    pub quote_mint: InterfaceAccount<'info, Mint>,
+    pub pool_base_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub pool_quote_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub protocol_fee_recipient: &'info AccountInfo<'info>,
+    pub protocol_fee_recipient_token_account: &'info AccountInfo<'info>,
+    pub base_token_program: &'info AccountInfo<'info>,
+    pub quote_token_program: &'info AccountInfo<'info>,
+    pub system_program: &'info AccountInfo<'info>,
+    pub associated_token_program: &'info AccountInfo<'info>,
+    pub event_authority: &'info AccountInfo<'info>,
+    pub coin_creator_vault_ata: &'info AccountInfo<'info>,
+    pub coin_creator_vault_authority: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_LEN: usize = 19;
+
+impl<'info> PumpSwapAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            pool,
+            global_config,
+            base_mint,
+            quote_mint,
+            pool_base_token_account,
+            pool_quote_token_account,
+            protocol_fee_recipient,
+            protocol_fee_recipient_token_account,
+            base_token_program,
+            quote_token_program,
+            system_program,
+            associated_token_program,
+            event_authority,
+            coin_creator_vault_ata,
+            coin_creator_vault_authority,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            pool,
+            global_config,
+            base_mint: InterfaceAccount::try_from(base_mint)?,
+            quote_mint: InterfaceAccount::try_from(quote_mint)?,
+            pool_base_token_account: InterfaceAccount::try_from(pool_base_token_account)?,
+            pool_quote_token_account: InterfaceAccount::try_from(pool_quote_token_account)?,
+            protocol_fee_recipient,
+            protocol_fee_recipient_token_account,
+            base_token_program,
+            quote_token_program,
+            system_program,
+            associated_token_program,
+            event_authority,
+            coin_creator_vault_ata,
+            coin_creator_vault_authority,
+        })
+    }
+
+    // lp_fee_basis_points + protocol_fee_basis_points + coin_creator_fee_basis_points
+    fn get_total_fee_bps(&self) -> Result<u128> {
+        let data = &self.global_config.try_borrow_data()?;
+        require!(data.len() >= 321, ErrorCode::InvalidPool);
+        let lp_fee_bps = u64::from_le_bytes(*array_ref![data, 40, 8]);
+        let protocol_fee_bps = u64::from_le_bytes(*array_ref![data, 48, 8]);
+        let coin_creator_fee_bps = u64::from_le_bytes(*array_ref![data, 313, 8]);
+        Ok(lp_fee_bps as u128 + protocol_fee_bps as u128 + coin_creator_fee_bps as u128)
+    }
+
+    // base amount out for an exact quote amount in, fees are charged on top of the quote amount
+    fn cal_base_amount_out(&self, quote_amount_in: u64) -> Result<u64> {
+        let base_reserve = self.pool_base_token_account.amount as u128;
+        let quote_reserve = self.pool_quote_token_account.amount as u128;
+        let total_fee_bps = self.get_total_fee_bps()?;
+
+        // leave room for each fee being rounded up
+        let effective_quote_in = (quote_amount_in as u128)
+            .checked_mul(FEE_DENOMINATOR)
+            .ok_or(ErrorCode::CalculationError)?
+            .checked_div(FEE_DENOMINATOR + total_fee_bps)
+            .ok_or(ErrorCode::CalculationError)?
+            .saturating_sub(3);
+        let base_amount_out = base_reserve
+            .checked_mul(effective_quote_in)
+            .ok_or(ErrorCode::CalculationError)?
+            .checked_div(
+                quote_reserve
+                    .checked_add(effective_quote_in)
+                    .ok_or(ErrorCode::CalculationError)?,
+            )
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(base_amount_out as u64)
+    }
+}
+
+pub fn buy<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::PumpSwapBuy amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = PumpSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &pumpswap_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    // buy: quote -> base
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.quote_mint.key()
+            && swap_accounts.swap_destination_token.mint == swap_accounts.base_mint.key(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    let base_amount_out = swap_accounts.cal_base_amount_out(amount_in)?;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(PUMPFUN_BUY_SELECTOR);
+    data.extend_from_slice(&base_amount_out.to_le_bytes()); // base_amount_out
+    data.extend_from_slice(&amount_in.to_le_bytes()); // max_quote_amount_in
+
+    let accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.pool.key(), false),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.global_config.key(), false),
+        AccountMeta::new_readonly(swap_accounts.base_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.quote_mint.key(), false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_accounts.pool_base_token_account.key(), false),
+        AccountMeta::new(swap_accounts.pool_quote_token_account.key(), false),
+        AccountMeta::new_readonly(swap_accounts.protocol_fee_recipient.key(), false),
+        AccountMeta::new(swap_accounts.protocol_fee_recipient_token_account.key(), false),
+        AccountMeta::new_readonly(swap_accounts.base_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.quote_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.system_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.associated_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.event_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.dex_program_id.key(), false),
+        AccountMeta::new(swap_accounts.coin_creator_vault_ata.key(), false),
+        AccountMeta::new_readonly(swap_accounts.coin_creator_vault_authority.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.pool.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.global_config.to_account_info(),
+        swap_accounts.base_mint.to_account_info(),
+        swap_accounts.quote_mint.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.pool_base_token_account.to_account_info(),
+        swap_accounts.pool_quote_token_account.to_account_info(),
+        swap_accounts.protocol_fee_recipient.to_account_info(),
+        swap_accounts.protocol_fee_recipient_token_account.to_account_info(),
+        swap_accounts.base_token_program.to_account_info(),
+        swap_accounts.quote_token_program.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        swap_accounts.associated_token_program.to_account_info(),
+        swap_accounts.event_authority.to_account_info(),
+        swap_accounts.dex_program_id.to_account_info(),
+        swap_accounts.coin_creator_vault_ata.to_account_info(),
+        swap_accounts.coin_creator_vault_authority.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &PumpSwapProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+pub fn sell<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::PumpSwapSell amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = PumpSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &pumpswap_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    // sell: base -> quote
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.base_mint.key()
+            && swap_accounts.swap_destination_token.mint == swap_accounts.quote_mint.key(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    let min_quote_amount_out = 1u64;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(PUMPFUN_SELL_SELECTOR);
+    data.extend_from_slice(&amount_in.to_le_bytes()); // base_amount_in
+    data.extend_from_slice(&min_quote_amount_out.to_le_bytes()); // min_quote_amount_out
+
+    let accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.pool.key(), false),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.global_config.key(), false),
+        AccountMeta::new_readonly(swap_accounts.base_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.quote_mint.key(), false),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_accounts.pool_base_token_account.key(), false),
+        AccountMeta::new(swap_accounts.pool_quote_token_account.key(), false),
+        AccountMeta::new_readonly(swap_accounts.protocol_fee_recipient.key(), false),
+        AccountMeta::new(swap_accounts.protocol_fee_recipient_token_account.key(), false),
+        AccountMeta::new_readonly(swap_accounts.base_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.quote_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.system_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.associated_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.event_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.dex_program_id.key(), false),
+        AccountMeta::new(swap_accounts.coin_creator_vault_ata.key(), false),
+        AccountMeta::new_readonly(swap_accounts.coin_creator_vault_authority.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.pool.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.global_config.to_account_info(),
+        swap_accounts.base_mint.to_account_info(),
+        swap_accounts.quote_mint.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.pool_base_token_account.to_account_info(),
+        swap_accounts.pool_quote_token_account.to_account_info(),
+        swap_accounts.protocol_fee_recipient.to_account_info(),
+        swap_accounts.protocol_fee_recipient_token_account.to_account_info(),
+        swap_accounts.base_token_program.to_account_info(),
+        swap_accounts.quote_token_program.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        swap_accounts.associated_token_program.to_account_info(),
+        swap_accounts.event_authority.to_account_info(),
+        swap_accounts.dex_program_id.to_account_info(),
+        swap_accounts.coin_creator_vault_ata.to_account_info(),
+        swap_accounts.coin_creator_vault_authority.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &PumpSwapProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_buy_instruction() {
+        let base_amount_out = 100u64;
+        let max_quote_amount_in = 1000u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(PUMPFUN_BUY_SELECTOR);
+        data.extend_from_slice(&base_amount_out.to_le_bytes());
+        data.extend_from_slice(&max_quote_amount_in.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+
+    #[test]
+    pub fn test_pack_sell_instruction() {
+        let base_amount_in = 100u64;
+        let min_quote_amount_out = 1u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(PUMPFUN_SELL_SELECTOR);
+        data.extend_from_slice(&base_amount_in.to_le_bytes());
+        data.extend_from_slice(&min_quote_amount_out.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
-use crate::{raydium_clmm_program, raydium_stable_program, raydium_swap_program, raydium_cpmm_program, HopAccounts, SWAP_SELECTOR, SWAP_V2_SELECTOR, CPSWAP_SELECTOR, ZERO_ADDRESS};
+use crate::{raydium_clmm_program, raydium_stable_program, raydium_swap_program, raydium_cpmm_program, ClmmParams, HopAccounts, SWAP_SELECTOR, SWAP_V2_SELECTOR, CPSWAP_SELECTOR, ZERO_ADDRESS};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
@@ -40,6 +40,21 @@ pub struct RaydiumSwapAccounts<'info> {
 }
 pub const ACCOUNTS_LEN: usize = 19;
 
+// swap_base_in_v2 drops the OpenBook market accounts
+pub struct RaydiumSwapV2Accounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub amm_id: &'info AccountInfo<'info>,
+    pub amm_authority: &'info AccountInfo<'info>,
+    pub pool_coin_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub pool_pc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+pub const V2_ACCOUNTS_LEN: usize = 9;
+
 pub struct RaydiumStableAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
     pub swap_authority_pubkey: &'info AccountInfo<'info>,
@@ -75,13 +90,16 @@ pub struct RaydiumClmmAccounts<'info> {
     pub input_vault: &'info AccountInfo<'info>,
     pub output_vault: &'info AccountInfo<'info>,
     pub observation_id: &'info AccountInfo<'info>,
-    pub tick_array0: &'info AccountInfo<'info>,
-    pub ex_bitmap: &'info AccountInfo<'info>,
-    pub tick_array1: &'info AccountInfo<'info>,
-    pub tick_array2: &'info AccountInfo<'info>,
     pub token_program: Program<'info, Token>,
+    pub ex_bitmap: &'info AccountInfo<'info>,
+    pub tick_arrays: Vec<&'info AccountInfo<'info>>,
 }
+// Fixed layout: ..., observation_id, tick_array0, ex_bitmap, tick_array1, tick_array2, token_program
 pub const CLMM_ACCOUNTS_LEN: usize = 14;
+// Variable layout: ..., observation_id, token_program, ex_bitmap, tick_array0..N
+pub const CLMM_FIXED_ACCOUNTS_LEN: usize = 11;
+pub const MAX_CLMM_TICK_ARRAYS_LEN: usize = 10;
+pub const MAX_CLMM_ACCOUNTS_LEN: usize = CLMM_FIXED_ACCOUNTS_LEN + MAX_CLMM_TICK_ARRAYS_LEN;
 
 pub struct RaydiumClmmV2Accounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -100,11 +118,11 @@ pub struct RaydiumClmmV2Accounts<'info> {
     pub input_vault_mint: InterfaceAccount<'info, Mint>,
     pub output_vault_mint: InterfaceAccount<'info, Mint>,
     pub ex_bitmap: &'info AccountInfo<'info>,
-    pub tick_array0: &'info AccountInfo<'info>,
-    pub tick_array1: &'info AccountInfo<'info>,
-    pub tick_array2: &'info AccountInfo<'info>,
+    pub tick_arrays: Vec<&'info AccountInfo<'info>>,
 }
 pub const CLMM_V2_ACCOUNTS_LEN: usize = 18;
+pub const CLMM_V2_FIXED_ACCOUNTS_LEN: usize = 15;
+pub const MAX_CLMM_V2_ACCOUNTS_LEN: usize = CLMM_V2_FIXED_ACCOUNTS_LEN + MAX_CLMM_TICK_ARRAYS_LEN;
 
 pub struct RaydiumCpmmAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -173,6 +191,34 @@ impl<'info> RaydiumSwapAccounts<'info> {
     }
 }
 
+impl<'info> RaydiumSwapV2Accounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            token_program,
+            amm_id,
+            amm_authority,
+            pool_coin_token_account,
+            pool_pc_token_account,
+        ]: & [AccountInfo<'info>; V2_ACCOUNTS_LEN] = array_ref![accounts, offset, V2_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            token_program: Program::try_from(token_program)?,
+            amm_id,
+            amm_authority,
+            pool_coin_token_account: Box::new(InterfaceAccount::try_from(pool_coin_token_account)?),
+            pool_pc_token_account: Box::new(InterfaceAccount::try_from(pool_pc_token_account)?),
+        })
+    }
+}
+
 impl<'info> RaydiumStableAccounts<'info> {
     fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
         let [
@@ -222,7 +268,11 @@ impl<'info> RaydiumStableAccounts<'info> {
 }
 
 impl<'info> RaydiumClmmAccounts<'info> {
-    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+    fn parse_accounts(
+        accounts: &'info [AccountInfo<'info>],
+        offset: usize,
+        accounts_len: Option<usize>,
+    ) -> Result<Self> {
         let [
             dex_program_id,
             swap_authority_pubkey,
@@ -233,12 +283,22 @@ impl<'info> RaydiumClmmAccounts<'info> {
             input_vault,
             output_vault,
             observation_id,
-            tick_array0,
-            ex_bitmap,
-            tick_array1,
-            tick_array2,
-            token_program,
-      ]: & [AccountInfo<'info>; CLMM_ACCOUNTS_LEN] = array_ref![accounts, offset, CLMM_ACCOUNTS_LEN];
+      ]: & [AccountInfo<'info>; 9] = array_ref![accounts, offset, 9];
+        let (token_program, ex_bitmap, tick_arrays) = match accounts_len {
+            None => {
+                let [tick_array0, ex_bitmap, tick_array1, tick_array2, token_program]: &[AccountInfo<'info>; 5] =
+                    array_ref![accounts, offset + 9, 5];
+                (token_program, ex_bitmap, vec![tick_array0, tick_array1, tick_array2])
+            }
+            Some(len) => {
+                let [token_program, ex_bitmap]: &[AccountInfo<'info>; 2] =
+                    array_ref![accounts, offset + 9, 2];
+                let tick_arrays = accounts[offset + CLMM_FIXED_ACCOUNTS_LEN..offset + len]
+                    .iter()
+                    .collect();
+                (token_program, ex_bitmap, tick_arrays)
+            }
+        };
 
         Ok(Self {
             dex_program_id,
@@ -250,17 +310,19 @@ impl<'info> RaydiumClmmAccounts<'info> {
             input_vault,
             output_vault,
             observation_id,
-            tick_array0,
-            ex_bitmap,
-            tick_array1,
-            tick_array2,
             token_program: Program::try_from(token_program)?,
+            ex_bitmap,
+            tick_arrays,
         })
     }
 }
 
 impl<'info> RaydiumClmmV2Accounts<'info> {
-    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+    fn parse_accounts(
+        accounts: &'info [AccountInfo<'info>],
+        offset: usize,
+        accounts_len: usize,
+    ) -> Result<Self> {
         let [
             dex_program_id,
             swap_authority_pubkey,
@@ -277,11 +339,10 @@ impl<'info> RaydiumClmmV2Accounts<'info> {
             input_vault_mint,
             output_vault_mint,
             ex_bitmap,
-            tick_array0,
-            tick_array1,
-            tick_array2,
-        
-      ]: & [AccountInfo<'info>; CLMM_V2_ACCOUNTS_LEN] = array_ref![accounts, offset, CLMM_V2_ACCOUNTS_LEN];
+      ]: & [AccountInfo<'info>; CLMM_V2_FIXED_ACCOUNTS_LEN] = array_ref![accounts, offset, CLMM_V2_FIXED_ACCOUNTS_LEN];
+        let tick_arrays = accounts[offset + CLMM_V2_FIXED_ACCOUNTS_LEN..offset + accounts_len]
+            .iter()
+            .collect();
 
         Ok(Self {
             dex_program_id,
@@ -299,9 +360,7 @@ impl<'info> RaydiumClmmV2Accounts<'info> {
             input_vault_mint: InterfaceAccount::try_from(input_vault_mint)?,
             output_vault_mint: InterfaceAccount::try_from(output_vault_mint)?,
             ex_bitmap,
-            tick_array0,
-            tick_array1,
-            tick_array2,
+            tick_arrays,
         })
     }
 }
@@ -452,6 +511,93 @@ pub fn swap<'a>(
     Ok(amount_out)
 }
 
+pub fn swap_v2<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::RaydiumSwapV2 amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + V2_ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = RaydiumSwapV2Accounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &raydium_swap_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.push(16);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&1u64.to_le_bytes());
+
+    let accounts = vec![
+        // spl token
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+        // amm
+        AccountMeta::new(swap_accounts.amm_id.key(), false),
+        AccountMeta::new_readonly(swap_accounts.amm_authority.key(), false),
+        AccountMeta::new(swap_accounts.pool_coin_token_account.key(), false),
+        AccountMeta::new(swap_accounts.pool_pc_token_account.key(), false),
+        // user
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.token_program.to_account_info(),
+        swap_accounts.amm_id.to_account_info(),
+        swap_accounts.amm_authority.to_account_info(),
+        swap_accounts.pool_coin_token_account.to_account_info(),
+        swap_accounts.pool_pc_token_account.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &RaydiumSwapProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        V2_ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
 pub fn swap_stable<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -560,6 +706,8 @@ pub fn swap_stable<'a>(
     Ok(amount_out)
 }
 
+// accounts_len selects the variable tick arrays layout, None for the fixed layout
+#[allow(clippy::too_many_arguments)]
 pub fn swap_clmm<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -567,18 +715,23 @@ pub fn swap_clmm<'a>(
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
+    accounts_len: Option<usize>,
+    params: ClmmParams,
 ) -> Result<u64> {
     msg!(
         "Dex::RaydiumClmmSwap amount_in: {}, offset: {}",
         amount_in,
         offset
     );
+    let account_len = accounts_len.unwrap_or(CLMM_ACCOUNTS_LEN);
     require!(
-        remaining_accounts.len() >= *offset + CLMM_ACCOUNTS_LEN,
+        account_len > CLMM_FIXED_ACCOUNTS_LEN
+            && remaining_accounts.len() >= *offset + account_len,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = RaydiumClmmAccounts::parse_accounts(remaining_accounts, *offset)?;
+    let mut swap_accounts =
+        RaydiumClmmAccounts::parse_accounts(remaining_accounts, *offset, accounts_len)?;
     if swap_accounts.dex_program_id.key != &raydium_clmm_program::id() {
         return Err(ErrorCode::InvalidProgramId.into());
     }
@@ -596,8 +749,10 @@ pub fn swap_clmm<'a>(
     )?;
 
     let is_base_input = true;
-    let sqrt_price_limit_x64 = 0u128;
-    let other_amount_threshold = 1u64;
+    let ClmmParams {
+        other_amount_threshold,
+        sqrt_price_limit_x64,
+    } = params;
 
     let mut data = Vec::with_capacity(ARGS_CLMM_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
@@ -616,8 +771,6 @@ pub fn swap_clmm<'a>(
         AccountMeta::new(swap_accounts.output_vault.key(), false),
         AccountMeta::new(swap_accounts.observation_id.key(), false),
         AccountMeta::new_readonly(swap_accounts.token_program.key(), false), // spl token
-        AccountMeta::new(swap_accounts.tick_array0.key(), false),
-        AccountMeta::new(swap_accounts.ex_bitmap.key(), false),
     ];
 
     let mut account_infos = vec![
@@ -630,22 +783,26 @@ pub fn swap_clmm<'a>(
         swap_accounts.output_vault.to_account_info(),
         swap_accounts.observation_id.to_account_info(),
         swap_accounts.token_program.to_account_info(),
-        swap_accounts.tick_array0.to_account_info(),
-        swap_accounts.ex_bitmap.to_account_info(),
     ];
 
-    let tick_array1 = swap_accounts.tick_array1.key();
-    let tick_array2 = swap_accounts.tick_array2.key();
-    if tick_array1 != ZERO_ADDRESS {
-        accounts.push(AccountMeta::new(tick_array1, false));
-        account_infos.push(swap_accounts.tick_array1.to_account_info());
+    // the first tick array is required, the bitmap extension and the others are optional
+    let (tick_array0, tick_arrays) = swap_accounts
+        .tick_arrays
+        .split_first()
+        .ok_or(ErrorCode::InvalidAccountsLength)?;
+    accounts.push(AccountMeta::new(tick_array0.key(), false));
+    account_infos.push(tick_array0.to_account_info());
+    if swap_accounts.ex_bitmap.key() != ZERO_ADDRESS {
+        accounts.push(AccountMeta::new(swap_accounts.ex_bitmap.key(), false));
+        account_infos.push(swap_accounts.ex_bitmap.to_account_info());
     }
-    if tick_array2 != ZERO_ADDRESS {
-        accounts.push(AccountMeta::new(tick_array2, false));
-        account_infos.push(swap_accounts.tick_array2.to_account_info());
+    for tick_array in tick_arrays.iter() {
+        if tick_array.key() != ZERO_ADDRESS {
+            accounts.push(AccountMeta::new(tick_array.key(), false));
+            account_infos.push(tick_array.to_account_info());
+        }
     }
 
-
     let instruction = Instruction {
         program_id: swap_accounts.dex_program_id.key(),
         accounts,
@@ -662,12 +819,14 @@ pub fn swap_clmm<'a>(
         instruction,
         hop,
         offset,
-        CLMM_ACCOUNTS_LEN,
+        account_len,
         proxy_swap   
     )?;
     Ok(amount_out)
 }
 
+// accounts_len allows 1..N tick arrays, None for the fixed three
+#[allow(clippy::too_many_arguments)]
 pub fn swap_clmm_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -675,18 +834,23 @@ pub fn swap_clmm_v2<'a>(
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
+    accounts_len: Option<usize>,
+    params: ClmmParams,
 ) -> Result<u64> {
     msg!(
         "Dex::RaydiumClmmSwapV2 amount_in: {}, offset: {}",
         amount_in,
         offset
     );
+    let account_len = accounts_len.unwrap_or(CLMM_V2_ACCOUNTS_LEN);
     require!(
-        remaining_accounts.len() >= *offset + CLMM_V2_ACCOUNTS_LEN,
+        account_len > CLMM_V2_FIXED_ACCOUNTS_LEN
+            && remaining_accounts.len() >= *offset + account_len,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = RaydiumClmmV2Accounts::parse_accounts(remaining_accounts, *offset)?;
+    let mut swap_accounts =
+        RaydiumClmmV2Accounts::parse_accounts(remaining_accounts, *offset, account_len)?;
     if swap_accounts.dex_program_id.key != &raydium_clmm_program::id() {
         return Err(ErrorCode::InvalidProgramId.into());
     }
@@ -704,8 +868,10 @@ pub fn swap_clmm_v2<'a>(
     )?;
 
     let is_base_input = true;
-    let sqrt_price_limit_x64 = 0u128;
-    let other_amount_threshold = 1u64;
+    let ClmmParams {
+        other_amount_threshold,
+        sqrt_price_limit_x64,
+    } = params;
 
     let mut data = Vec::with_capacity(ARGS_CLMM_LEN);
     data.extend_from_slice(SWAP_V2_SELECTOR);
@@ -728,8 +894,6 @@ pub fn swap_clmm_v2<'a>(
         AccountMeta::new_readonly(swap_accounts.memo_program.key(), false), 
         AccountMeta::new_readonly(swap_accounts.input_vault_mint.key(), false), 
         AccountMeta::new_readonly(swap_accounts.output_vault_mint.key(), false),
-        AccountMeta::new(swap_accounts.ex_bitmap.key(), false),
-        AccountMeta::new(swap_accounts.tick_array0.key(), false),
     ];
 
     let mut account_infos = vec![
@@ -746,19 +910,24 @@ pub fn swap_clmm_v2<'a>(
         swap_accounts.memo_program.to_account_info(),
         swap_accounts.input_vault_mint.to_account_info(),
         swap_accounts.output_vault_mint.to_account_info(),
-        swap_accounts.ex_bitmap.to_account_info(),
-        swap_accounts.tick_array0.to_account_info(),
     ];
 
-    let tick_array1 = swap_accounts.tick_array1.key();
-    let tick_array2 = swap_accounts.tick_array2.key();
-    if tick_array1 != ZERO_ADDRESS {
-        accounts.push(AccountMeta::new(tick_array1, false));
-        account_infos.push(swap_accounts.tick_array1.to_account_info());
+    // the bitmap extension is optional, the first tick array is required
+    if swap_accounts.ex_bitmap.key() != ZERO_ADDRESS {
+        accounts.push(AccountMeta::new(swap_accounts.ex_bitmap.key(), false));
+        account_infos.push(swap_accounts.ex_bitmap.to_account_info());
     }
-    if tick_array2 != ZERO_ADDRESS {
-        accounts.push(AccountMeta::new(tick_array2, false));
-        account_infos.push(swap_accounts.tick_array2.to_account_info());
+    let (tick_array0, tick_arrays) = swap_accounts
+        .tick_arrays
+        .split_first()
+        .ok_or(ErrorCode::InvalidAccountsLength)?;
+    accounts.push(AccountMeta::new(tick_array0.key(), false));
+    account_infos.push(tick_array0.to_account_info());
+    for tick_array in tick_arrays.iter() {
+        if tick_array.key() != ZERO_ADDRESS {
+            accounts.push(AccountMeta::new(tick_array.key(), false));
+            account_infos.push(tick_array.to_account_info());
+        }
     }
 
     let instruction = Instruction {
@@ -777,7 +946,7 @@ pub fn swap_clmm_v2<'a>(
         instruction,
         hop,
         offset,
-        CLMM_V2_ACCOUNTS_LEN,
+        account_len,
         proxy_swap,
     )?;
     Ok(amount_out)
@@ -895,6 +1064,18 @@ mod tests {
         assert!(data.len() == ARGS_LEN);
     }
 
+    #[test]
+    pub fn test_pack_swap_v2_instruction() {
+        let amount_in = 100u64;
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.push(16);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&1u64.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+
     #[test]
     pub fn test_pack_clmm_instruction() {
         let amount_in = 100u64;
@@ -0,0 +1,286 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{
+    raydium_launchlab_program, HopAccounts, BUY_EXACT_IN_SELECTOR, SELL_EXACT_IN_SELECTOR,
+};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 32;
+
+pub struct RaydiumLaunchlabProcessor;
+impl DexProcessor for RaydiumLaunchlabProcessor {}
+
+pub struct RaydiumLaunchlabAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: &'info AccountInfo<'info>,
+    pub global_config: &'info AccountInfo<'info>,
+    pub platform_config: &'info AccountInfo<'info>,
+    pub pool_state: &'info AccountInfo<'info>,
+    pub base_vault: &'info AccountInfo<'info>,
+    pub quote_vault: &'info AccountInfo<'info>,
+    pub base_token_mint: InterfaceAccount<'info, Mint>,
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+    pub base_token_program: &'info AccountInfo<'info>,
+    pub quote_token_program: &'info AccountInfo<'info>,
+    pub event_authority: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_LEN: usize = 15;
+
+impl<'info> RaydiumLaunchlabAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            authority,
+            global_config,
+            platform_config,
+            pool_state,
+            base_vault,
+            quote_vault,
+            base_token_mint,
+            quote_token_mint,
+            base_token_program,
+            quote_token_program,
+            event_authority,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            authority,
+            global_config,
+            platform_config,
+            pool_state,
+            base_vault,
+            quote_vault,
+            base_token_mint: InterfaceAccount::try_from(base_token_mint)?,
+            quote_token_mint: InterfaceAccount::try_from(quote_token_mint)?,
+            base_token_program,
+            quote_token_program,
+            event_authority,
+        })
+    }
+}
+
+pub fn buy<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::RaydiumLaunchlabBuy amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    swap_exact_in(
+        remaining_accounts,
+        amount_in,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        true,
+    )
+}
+
+pub fn sell<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::RaydiumLaunchlabSell amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    swap_exact_in(
+        remaining_accounts,
+        amount_in,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        false,
+    )
+}
+
+fn swap_exact_in<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    is_buy: bool,
+) -> Result<u64> {
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = RaydiumLaunchlabAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &raydium_launchlab_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    // buy: quote -> base, sell: base -> quote
+    // the quote leg is a wSOL token account for SOL pools, so no account is closed
+    let (user_base_token, user_quote_token, selector) = if is_buy {
+        require!(
+            swap_accounts.swap_source_token.mint == swap_accounts.quote_token_mint.key()
+                && swap_accounts.swap_destination_token.mint
+                    == swap_accounts.base_token_mint.key(),
+            ErrorCode::InvalidTokenMint
+        );
+        (
+            &swap_accounts.swap_destination_token,
+            &swap_accounts.swap_source_token,
+            BUY_EXACT_IN_SELECTOR,
+        )
+    } else {
+        require!(
+            swap_accounts.swap_source_token.mint == swap_accounts.base_token_mint.key()
+                && swap_accounts.swap_destination_token.mint
+                    == swap_accounts.quote_token_mint.key(),
+            ErrorCode::InvalidTokenMint
+        );
+        (
+            &swap_accounts.swap_source_token,
+            &swap_accounts.swap_destination_token,
+            SELL_EXACT_IN_SELECTOR,
+        )
+    };
+
+    let minimum_amount_out = 1u64;
+    let share_fee_rate = 0u64;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(selector);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    data.extend_from_slice(&share_fee_rate.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true), // payer
+        AccountMeta::new_readonly(swap_accounts.authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.global_config.key(), false),
+        AccountMeta::new_readonly(swap_accounts.platform_config.key(), false),
+        AccountMeta::new(swap_accounts.pool_state.key(), false),
+        AccountMeta::new(user_base_token.key(), false),
+        AccountMeta::new(user_quote_token.key(), false),
+        AccountMeta::new(swap_accounts.base_vault.key(), false),
+        AccountMeta::new(swap_accounts.quote_vault.key(), false),
+        AccountMeta::new_readonly(swap_accounts.base_token_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.quote_token_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.base_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.quote_token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.event_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.dex_program_id.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.authority.to_account_info(),
+        swap_accounts.global_config.to_account_info(),
+        swap_accounts.platform_config.to_account_info(),
+        swap_accounts.pool_state.to_account_info(),
+        user_base_token.to_account_info(),
+        user_quote_token.to_account_info(),
+        swap_accounts.base_vault.to_account_info(),
+        swap_accounts.quote_vault.to_account_info(),
+        swap_accounts.base_token_mint.to_account_info(),
+        swap_accounts.quote_token_mint.to_account_info(),
+        swap_accounts.base_token_program.to_account_info(),
+        swap_accounts.quote_token_program.to_account_info(),
+        swap_accounts.event_authority.to_account_info(),
+        swap_accounts.dex_program_id.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &RaydiumLaunchlabProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_buy_exact_in_instruction() {
+        let amount_in = 100u64;
+        let minimum_amount_out = 1u64;
+        let share_fee_rate = 0u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(BUY_EXACT_IN_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+        data.extend_from_slice(&share_fee_rate.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+
+    #[test]
+    pub fn test_pack_sell_exact_in_instruction() {
+        let amount_in = 100u64;
+        let minimum_amount_out = 1u64;
+        let share_fee_rate = 0u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(SELL_EXACT_IN_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+        data.extend_from_slice(&share_fee_rate.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
@@ -12,8 +12,8 @@ use super::common::DexProcessor;
 pub struct SanctumProcessor;
 impl DexProcessor for SanctumProcessor {}
 
-const ADD_LST_LIQ_ACCOUNTS_LEN: usize = 18;
-const ADD_WSOL_LIQ_ACCOUNTS_LEN: usize = 14;
+pub const ADD_LST_LIQ_ACCOUNTS_LEN: usize = 18;
+pub const ADD_WSOL_LIQ_ACCOUNTS_LEN: usize = 14;
 const ADD_REMOVE_LIQ_ARGS_LEN: usize = 1 //discriminant
                             + 1 //lst_value_calc_accs
                             + 4 //lst_index
@@ -27,10 +27,10 @@ const SWAP_EXACT_IN_ARGS_LEN: usize = 1 //discriminant
                             + 8 //min_lp_out
                             + 8; //lst_amount
 
-const REMOVE_LST_LIQ_ACCOUNTS_LEN: usize = 19;
-const REMOVE_WSOL_LIQ_ACCOUNTS_LEN: usize = 15;
-const SWAP_LST_SOL_ACCOUNTS_LEN: usize = 22;
-const SWAP_LST_LST_ACCOUNTS_LEN: usize = 26;
+pub const REMOVE_LST_LIQ_ACCOUNTS_LEN: usize = 19;
+pub const REMOVE_WSOL_LIQ_ACCOUNTS_LEN: usize = 15;
+pub const SWAP_LST_SOL_ACCOUNTS_LEN: usize = 22;
+pub const SWAP_LST_LST_ACCOUNTS_LEN: usize = 26;
 
 pub enum SanctumRemoveLiqAccounts<'info> {
     WSOL(SanctumRemoveWsolLiqAccounts<'info>),
@@ -849,7 +849,7 @@ pub fn swap_without_wsol_handler<'a>(
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, AnchorDeserialize, AnchorSerialize, PartialEq, Pod, Copy, Zeroable)]
+#[derive(Clone, Debug, AnchorDeserialize, AnchorSerialize, PartialEq, Copy)]
 pub struct LstState {
     pub is_input_disabled: u8,
     pub pool_reserves_bump: u8,
@@ -860,6 +860,10 @@ pub struct LstState {
     pub sol_value_calculator: Pubkey,
 }
 
+// SAFETY: repr(C) with only plain old data fields and no padding (8 + 8 + 32 + 32 bytes)
+unsafe impl Zeroable for LstState {}
+unsafe impl Pod for LstState {}
+
 /// Tries to reinterpret `list_acc_data` bytes as a slice.
 ///
 /// `list_acc_data` should only contain data of the items, no headers etc
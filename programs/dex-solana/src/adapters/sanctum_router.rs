@@ -0,0 +1,521 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{sanctum_router_program, wsol_program, HopAccounts, StakeBridgeParams};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const STAKE_WRAPPED_SOL_INSTRUCTION: u8 = 0;
+const SWAP_VIA_STAKE_INSTRUCTION: u8 = 1;
+const WITHDRAW_WRAPPED_SOL_INSTRUCTION: u8 = 8;
+const ARGS_LEN: usize = 9;
+const SWAP_VIA_STAKE_ARGS_LEN: usize = 13;
+
+pub struct SanctumRouterProcessor;
+impl DexProcessor for SanctumRouterProcessor {}
+
+// The fixed accounts are followed by the stake pool accounts of the LST, which differ between
+// stake pool programs, so the route must carry the accounts count
+pub const STAKE_WRAPPED_SOL_ACCOUNTS_LEN: usize = 11;
+pub const WITHDRAW_WRAPPED_SOL_ACCOUNTS_LEN: usize = 8;
+pub const SWAP_VIA_STAKE_ACCOUNTS_LEN: usize = 8;
+pub const MAX_POOL_ACCOUNTS_LEN: usize = 16;
+
+pub struct SanctumRouterStakeWrappedSolAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub wsol_bridge_in: &'info AccountInfo<'info>,
+    pub sol_bridge_out: &'info AccountInfo<'info>,
+    pub dest_token_fee_token_account: &'info AccountInfo<'info>,
+    pub dest_token_mint: InterfaceAccount<'info, Mint>,
+    pub wsol_mint: &'info AccountInfo<'info>,
+    pub token_program: &'info AccountInfo<'info>,
+    pub system_program: &'info AccountInfo<'info>,
+    pub pool_accounts: &'info [AccountInfo<'info>],
+}
+
+pub struct SanctumRouterWithdrawWrappedSolAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub wsol_fee_token_account: &'info AccountInfo<'info>,
+    pub src_token_mint: InterfaceAccount<'info, Mint>,
+    pub wsol_mint: &'info AccountInfo<'info>,
+    pub token_program: &'info AccountInfo<'info>,
+    pub pool_accounts: &'info [AccountInfo<'info>],
+}
+
+pub struct SanctumRouterSwapViaStakeAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub bridge_stake: &'info AccountInfo<'info>,
+    pub dest_token_fee_token_account: &'info AccountInfo<'info>,
+    pub src_token_mint: InterfaceAccount<'info, Mint>,
+    pub dest_token_mint: InterfaceAccount<'info, Mint>,
+    // withdraw stake accounts of the source pool, then deposit stake accounts of the destination pool
+    pub pool_accounts: &'info [AccountInfo<'info>],
+}
+
+impl<'info> SanctumRouterStakeWrappedSolAccounts<'info> {
+    fn parse_accounts(
+        accounts: &'info [AccountInfo<'info>],
+        offset: usize,
+        accounts_len: usize,
+    ) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            wsol_bridge_in,
+            sol_bridge_out,
+            dest_token_fee_token_account,
+            dest_token_mint,
+            wsol_mint,
+            token_program,
+            system_program,
+        ]: &[AccountInfo<'info>; STAKE_WRAPPED_SOL_ACCOUNTS_LEN] =
+            array_ref![accounts, offset, STAKE_WRAPPED_SOL_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            wsol_bridge_in,
+            sol_bridge_out,
+            dest_token_fee_token_account,
+            dest_token_mint: InterfaceAccount::try_from(dest_token_mint)?,
+            wsol_mint,
+            token_program,
+            system_program,
+            pool_accounts: &accounts
+                [offset + STAKE_WRAPPED_SOL_ACCOUNTS_LEN..offset + accounts_len],
+        })
+    }
+}
+
+impl<'info> SanctumRouterWithdrawWrappedSolAccounts<'info> {
+    fn parse_accounts(
+        accounts: &'info [AccountInfo<'info>],
+        offset: usize,
+        accounts_len: usize,
+    ) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            wsol_fee_token_account,
+            src_token_mint,
+            wsol_mint,
+            token_program,
+        ]: &[AccountInfo<'info>; WITHDRAW_WRAPPED_SOL_ACCOUNTS_LEN] =
+            array_ref![accounts, offset, WITHDRAW_WRAPPED_SOL_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            wsol_fee_token_account,
+            src_token_mint: InterfaceAccount::try_from(src_token_mint)?,
+            wsol_mint,
+            token_program,
+            pool_accounts: &accounts
+                [offset + WITHDRAW_WRAPPED_SOL_ACCOUNTS_LEN..offset + accounts_len],
+        })
+    }
+}
+
+impl<'info> SanctumRouterSwapViaStakeAccounts<'info> {
+    fn parse_accounts(
+        accounts: &'info [AccountInfo<'info>],
+        offset: usize,
+        accounts_len: usize,
+    ) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            bridge_stake,
+            dest_token_fee_token_account,
+            src_token_mint,
+            dest_token_mint,
+        ]: &[AccountInfo<'info>; SWAP_VIA_STAKE_ACCOUNTS_LEN] =
+            array_ref![accounts, offset, SWAP_VIA_STAKE_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            bridge_stake,
+            dest_token_fee_token_account,
+            src_token_mint: InterfaceAccount::try_from(src_token_mint)?,
+            dest_token_mint: InterfaceAccount::try_from(dest_token_mint)?,
+            pool_accounts: &accounts[offset + SWAP_VIA_STAKE_ACCOUNTS_LEN..offset + accounts_len],
+        })
+    }
+}
+
+// The stake pool accounts keep the writable flags set by the route
+fn push_pool_accounts<'info>(
+    pool_accounts: &'info [AccountInfo<'info>],
+    accounts: &mut Vec<AccountMeta>,
+    account_infos: &mut Vec<AccountInfo<'info>>,
+) {
+    for pool_account in pool_accounts.iter() {
+        if pool_account.is_writable {
+            accounts.push(AccountMeta::new(pool_account.key(), false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(pool_account.key(), false));
+        }
+        account_infos.push(pool_account.to_account_info());
+    }
+}
+
+pub fn stake_wrapped_sol<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    accounts_len: usize,
+) -> Result<u64> {
+    msg!(
+        "Dex::SanctumRouterStakeWrappedSol amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        accounts_len > STAKE_WRAPPED_SOL_ACCOUNTS_LEN
+            && remaining_accounts.len() >= *offset + accounts_len,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = SanctumRouterStakeWrappedSolAccounts::parse_accounts(
+        remaining_accounts,
+        *offset,
+        accounts_len,
+    )?;
+    if swap_accounts.dex_program_id.key != &sanctum_router_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == wsol_program::id()
+            && swap_accounts.wsol_mint.key() == wsol_program::id()
+            && swap_accounts.swap_destination_token.mint == swap_accounts.dest_token_mint.key(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.push(STAKE_WRAPPED_SOL_INSTRUCTION);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_accounts.wsol_bridge_in.key(), false),
+        AccountMeta::new(swap_accounts.sol_bridge_out.key(), false),
+        AccountMeta::new(swap_accounts.dest_token_fee_token_account.key(), false),
+        AccountMeta::new(swap_accounts.dest_token_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.wsol_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.system_program.key(), false),
+    ];
+
+    let mut account_infos = vec![
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.wsol_bridge_in.to_account_info(),
+        swap_accounts.sol_bridge_out.to_account_info(),
+        swap_accounts.dest_token_fee_token_account.to_account_info(),
+        swap_accounts.dest_token_mint.to_account_info(),
+        swap_accounts.wsol_mint.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+    ];
+    push_pool_accounts(
+        swap_accounts.pool_accounts,
+        &mut accounts,
+        &mut account_infos,
+    );
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SanctumRouterProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        accounts_len,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+pub fn withdraw_wrapped_sol<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    accounts_len: usize,
+) -> Result<u64> {
+    msg!(
+        "Dex::SanctumRouterWithdrawWrappedSol amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        accounts_len > WITHDRAW_WRAPPED_SOL_ACCOUNTS_LEN
+            && remaining_accounts.len() >= *offset + accounts_len,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = SanctumRouterWithdrawWrappedSolAccounts::parse_accounts(
+        remaining_accounts,
+        *offset,
+        accounts_len,
+    )?;
+    if swap_accounts.dex_program_id.key != &sanctum_router_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.src_token_mint.key()
+            && swap_accounts.swap_destination_token.mint == wsol_program::id()
+            && swap_accounts.wsol_mint.key() == wsol_program::id(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.push(WITHDRAW_WRAPPED_SOL_INSTRUCTION);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_accounts.wsol_fee_token_account.key(), false),
+        AccountMeta::new(swap_accounts.src_token_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.wsol_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+    ];
+
+    let mut account_infos = vec![
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.wsol_fee_token_account.to_account_info(),
+        swap_accounts.src_token_mint.to_account_info(),
+        swap_accounts.wsol_mint.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+    ];
+    push_pool_accounts(
+        swap_accounts.pool_accounts,
+        &mut accounts,
+        &mut account_infos,
+    );
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SanctumRouterProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        accounts_len,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn swap_via_stake<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    accounts_len: usize,
+    params: StakeBridgeParams,
+) -> Result<u64> {
+    msg!(
+        "Dex::SanctumRouterSwapViaStake amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        accounts_len > SWAP_VIA_STAKE_ACCOUNTS_LEN
+            && remaining_accounts.len() >= *offset + accounts_len,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = SanctumRouterSwapViaStakeAccounts::parse_accounts(
+        remaining_accounts,
+        *offset,
+        accounts_len,
+    )?;
+    if swap_accounts.dex_program_id.key != &sanctum_router_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.src_token_mint.key()
+            && swap_accounts.swap_destination_token.mint == swap_accounts.dest_token_mint.key(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    let mut data = Vec::with_capacity(SWAP_VIA_STAKE_ARGS_LEN);
+    data.push(SWAP_VIA_STAKE_INSTRUCTION);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&params.bridge_stake_seed.to_le_bytes());
+
+    let mut accounts = vec![
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_accounts.bridge_stake.key(), false),
+        AccountMeta::new(swap_accounts.dest_token_fee_token_account.key(), false),
+        AccountMeta::new(swap_accounts.src_token_mint.key(), false),
+        AccountMeta::new(swap_accounts.dest_token_mint.key(), false),
+    ];
+
+    let mut account_infos = vec![
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.bridge_stake.to_account_info(),
+        swap_accounts.dest_token_fee_token_account.to_account_info(),
+        swap_accounts.src_token_mint.to_account_info(),
+        swap_accounts.dest_token_mint.to_account_info(),
+    ];
+    push_pool_accounts(
+        swap_accounts.pool_accounts,
+        &mut accounts,
+        &mut account_infos,
+    );
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SanctumRouterProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        accounts_len,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_stake_wrapped_sol_instruction() {
+        let amount_in = 100u64;
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.push(STAKE_WRAPPED_SOL_INSTRUCTION);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+
+    #[test]
+    pub fn test_pack_swap_via_stake_instruction() {
+        let amount_in = 100u64;
+        let bridge_stake_seed = 1u32;
+        let mut data = Vec::with_capacity(SWAP_VIA_STAKE_ARGS_LEN);
+        data.push(SWAP_VIA_STAKE_INSTRUCTION);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&bridge_stake_seed.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == SWAP_VIA_STAKE_ARGS_LEN);
+    }
+}
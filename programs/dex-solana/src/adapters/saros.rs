@@ -0,0 +1,210 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{saros_dlmm_program, HopAccounts, SWAP_SELECTOR};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 26;
+const SWAP_TYPE_EXACT_INPUT: u8 = 0;
+
+pub struct SarosDlmmProcessor;
+impl DexProcessor for SarosDlmmProcessor {}
+
+pub struct SarosDlmmAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub pair: &'info AccountInfo<'info>,
+    pub token_mint_x: InterfaceAccount<'info, Mint>,
+    pub token_mint_y: InterfaceAccount<'info, Mint>,
+    pub bin_array_lower: &'info AccountInfo<'info>,
+    pub bin_array_upper: &'info AccountInfo<'info>,
+    pub token_vault_x: InterfaceAccount<'info, TokenAccount>,
+    pub token_vault_y: InterfaceAccount<'info, TokenAccount>,
+    pub token_program_x: &'info AccountInfo<'info>,
+    pub token_program_y: &'info AccountInfo<'info>,
+    pub memo_program: &'info AccountInfo<'info>,
+    pub event_authority: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_LEN: usize = 15;
+
+impl<'info> SarosDlmmAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            pair,
+            token_mint_x,
+            token_mint_y,
+            bin_array_lower,
+            bin_array_upper,
+            token_vault_x,
+            token_vault_y,
+            token_program_x,
+            token_program_y,
+            memo_program,
+            event_authority,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            pair,
+            token_mint_x: InterfaceAccount::try_from(token_mint_x)?,
+            token_mint_y: InterfaceAccount::try_from(token_mint_y)?,
+            bin_array_lower,
+            bin_array_upper,
+            token_vault_x: InterfaceAccount::try_from(token_vault_x)?,
+            token_vault_y: InterfaceAccount::try_from(token_vault_y)?,
+            token_program_x,
+            token_program_y,
+            memo_program,
+            event_authority,
+        })
+    }
+}
+
+pub fn swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::SarosDlmm amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = SarosDlmmAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &saros_dlmm_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    let (swap_for_y, user_vault_x, user_vault_y) = if swap_accounts.swap_source_token.mint
+        == swap_accounts.token_vault_x.mint
+        && swap_accounts.swap_destination_token.mint == swap_accounts.token_vault_y.mint
+    {
+        (true, swap_source_token, swap_destination_token)
+    } else if swap_accounts.swap_source_token.mint == swap_accounts.token_vault_y.mint
+        && swap_accounts.swap_destination_token.mint == swap_accounts.token_vault_x.mint
+    {
+        (false, swap_destination_token, swap_source_token)
+    } else {
+        return Err(ErrorCode::InvalidTokenMint.into());
+    };
+    let other_amount_threshold = 1u64;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(SWAP_SELECTOR);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&other_amount_threshold.to_le_bytes());
+    data.extend_from_slice(&(swap_for_y as u8).to_le_bytes());
+    data.extend_from_slice(&SWAP_TYPE_EXACT_INPUT.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.pair.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_mint_x.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_mint_y.key(), false),
+        AccountMeta::new(swap_accounts.bin_array_lower.key(), false),
+        AccountMeta::new(swap_accounts.bin_array_upper.key(), false),
+        AccountMeta::new(swap_accounts.token_vault_x.key(), false),
+        AccountMeta::new(swap_accounts.token_vault_y.key(), false),
+        AccountMeta::new(user_vault_x, false),
+        AccountMeta::new(user_vault_y, false),
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.token_program_x.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program_y.key(), false),
+        AccountMeta::new_readonly(swap_accounts.memo_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.event_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.dex_program_id.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.pair.to_account_info(),
+        swap_accounts.token_mint_x.to_account_info(),
+        swap_accounts.token_mint_y.to_account_info(),
+        swap_accounts.bin_array_lower.to_account_info(),
+        swap_accounts.bin_array_upper.to_account_info(),
+        swap_accounts.token_vault_x.to_account_info(),
+        swap_accounts.token_vault_y.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.token_program_x.to_account_info(),
+        swap_accounts.token_program_y.to_account_info(),
+        swap_accounts.memo_program.to_account_info(),
+        swap_accounts.event_authority.to_account_info(),
+        swap_accounts.dex_program_id.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SarosDlmmProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_swap_instruction() {
+        let amount_in = 100u64;
+        let other_amount_threshold = 1u64;
+        let swap_for_y = true;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(SWAP_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&other_amount_threshold.to_le_bytes());
+        data.extend_from_slice(&(swap_for_y as u8).to_le_bytes());
+        data.extend_from_slice(&SWAP_TYPE_EXACT_INPUT.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
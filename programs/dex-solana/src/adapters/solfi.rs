@@ -0,0 +1,173 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{solfi_program, HopAccounts};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::TokenAccount;
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 18;
+const SWAP_INSTRUCTION: u8 = 7;
+
+pub struct SolFiProcessor;
+impl DexProcessor for SolFiProcessor {}
+
+pub struct SolFiAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub pair: &'info AccountInfo<'info>,
+    pub pool_token_account_a: InterfaceAccount<'info, TokenAccount>,
+    pub pool_token_account_b: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'info AccountInfo<'info>,
+    pub sysvar_instructions: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_LEN: usize = 9;
+
+impl<'info> SolFiAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            pair,
+            pool_token_account_a,
+            pool_token_account_b,
+            token_program,
+            sysvar_instructions,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            pair,
+            pool_token_account_a: InterfaceAccount::try_from(pool_token_account_a)?,
+            pool_token_account_b: InterfaceAccount::try_from(pool_token_account_b)?,
+            token_program,
+            sysvar_instructions,
+        })
+    }
+}
+
+pub fn swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!("Dex::SolFi amount_in: {}, offset: {}", amount_in, offset);
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = SolFiAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &solfi_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    // direction 0: a -> b, 1: b -> a
+    let (direction, user_token_account_a, user_token_account_b) =
+        if swap_accounts.swap_source_token.mint == swap_accounts.pool_token_account_a.mint
+            && swap_accounts.swap_destination_token.mint == swap_accounts.pool_token_account_b.mint
+        {
+            (0u8, swap_source_token, swap_destination_token)
+        } else if swap_accounts.swap_source_token.mint == swap_accounts.pool_token_account_b.mint
+            && swap_accounts.swap_destination_token.mint == swap_accounts.pool_token_account_a.mint
+        {
+            (1u8, swap_destination_token, swap_source_token)
+        } else {
+            return Err(ErrorCode::InvalidTokenMint.into());
+        };
+    let minimum_amount_out = 0u64;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.push(SWAP_INSTRUCTION);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+    data.push(direction);
+
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_accounts.pair.key(), false),
+        AccountMeta::new(swap_accounts.pool_token_account_a.key(), false),
+        AccountMeta::new(swap_accounts.pool_token_account_b.key(), false),
+        AccountMeta::new(user_token_account_a, false),
+        AccountMeta::new(user_token_account_b, false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.sysvar_instructions.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.pair.to_account_info(),
+        swap_accounts.pool_token_account_a.to_account_info(),
+        swap_accounts.pool_token_account_b.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+        swap_accounts.sysvar_instructions.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SolFiProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_swap_instruction() {
+        let amount_in = 100u64;
+        let minimum_amount_out = 0u64;
+        let direction = 0u8;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.push(SWAP_INSTRUCTION);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+        data.push(direction);
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
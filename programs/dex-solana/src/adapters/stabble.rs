@@ -0,0 +1,236 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{stabble_stable_program, stabble_weighted_program, HopAccounts, SWAP_SELECTOR};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::TokenAccount;
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 25;
+
+pub struct StabbleProcessor;
+impl DexProcessor for StabbleProcessor {}
+
+pub struct StabbleAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub pool: &'info AccountInfo<'info>,
+    pub vault_token_in: InterfaceAccount<'info, TokenAccount>,
+    pub vault_token_out: InterfaceAccount<'info, TokenAccount>,
+    pub beneficiary_token_out: &'info AccountInfo<'info>,
+    pub withdraw_authority: &'info AccountInfo<'info>,
+    pub vault: &'info AccountInfo<'info>,
+    pub vault_authority: &'info AccountInfo<'info>,
+    pub vault_program: &'info AccountInfo<'info>,
+    pub token_program: &'info AccountInfo<'info>,
+    pub token_2022_program: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_LEN: usize = 14;
+
+impl<'info> StabbleAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            pool,
+            vault_token_in,
+            vault_token_out,
+            beneficiary_token_out,
+            withdraw_authority,
+            vault,
+            vault_authority,
+            vault_program,
+            token_program,
+            token_2022_program,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            pool,
+            vault_token_in: InterfaceAccount::try_from(vault_token_in)?,
+            vault_token_out: InterfaceAccount::try_from(vault_token_out)?,
+            beneficiary_token_out,
+            withdraw_authority,
+            vault,
+            vault_authority,
+            vault_program,
+            token_program,
+            token_2022_program,
+        })
+    }
+}
+
+pub fn swap_stable<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::StabbleStable amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    swap(
+        remaining_accounts,
+        amount_in,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        &stabble_stable_program::id(),
+    )
+}
+
+pub fn swap_weighted<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::StabbleWeighted amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    swap(
+        remaining_accounts,
+        amount_in,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        &stabble_weighted_program::id(),
+    )
+}
+
+// The stable and weighted pool programs share the same swap interface
+fn swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    program_id: &Pubkey,
+) -> Result<u64> {
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = StabbleAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != program_id {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.vault_token_in.mint
+            && swap_accounts.swap_destination_token.mint == swap_accounts.vault_token_out.mint,
+        ErrorCode::InvalidTokenMint
+    );
+
+    let minimum_amount_out = 1u64;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.extend_from_slice(SWAP_SELECTOR);
+    data.extend_from_slice(&Some(amount_in).try_to_vec()?);
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_accounts.vault_token_in.key(), false),
+        AccountMeta::new(swap_accounts.vault_token_out.key(), false),
+        AccountMeta::new(swap_accounts.beneficiary_token_out.key(), false),
+        AccountMeta::new(swap_accounts.pool.key(), false),
+        AccountMeta::new_readonly(swap_accounts.withdraw_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.vault.key(), false),
+        AccountMeta::new_readonly(swap_accounts.vault_authority.key(), false),
+        AccountMeta::new_readonly(swap_accounts.vault_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_2022_program.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.vault_token_in.to_account_info(),
+        swap_accounts.vault_token_out.to_account_info(),
+        swap_accounts.beneficiary_token_out.to_account_info(),
+        swap_accounts.pool.to_account_info(),
+        swap_accounts.withdraw_authority.to_account_info(),
+        swap_accounts.vault.to_account_info(),
+        swap_accounts.vault_authority.to_account_info(),
+        swap_accounts.vault_program.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+        swap_accounts.token_2022_program.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &StabbleProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_swap_instruction() {
+        let amount_in = 100u64;
+        let minimum_amount_out = 1u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.extend_from_slice(SWAP_SELECTOR);
+        data.extend_from_slice(&Some(amount_in).try_to_vec().unwrap());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
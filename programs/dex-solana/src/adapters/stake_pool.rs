@@ -0,0 +1,374 @@
+use crate::adapters::common::{before_check, invoke_process, SolInProcessor, SolOutProcessor};
+use crate::error::ErrorCode;
+use crate::utils::unwrap_wsol_to_authority;
+use crate::{spl_stake_pool_program, wsol_program, HopAccounts};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use arrayref::array_ref;
+
+const ARGS_LEN: usize = 9;
+const DEPOSIT_SOL_INSTRUCTION: u8 = 14;
+const WITHDRAW_SOL_INSTRUCTION: u8 = 16;
+
+// Pools with a sol deposit/withdraw authority are not supported, the optional account is not passed
+pub struct StakePoolDepositSolAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub stake_pool: &'info AccountInfo<'info>,
+    pub withdraw_authority: &'info AccountInfo<'info>,
+    pub reserve_stake: &'info AccountInfo<'info>,
+    pub manager_fee_account: &'info AccountInfo<'info>,
+    pub referrer_fee_account: &'info AccountInfo<'info>,
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub payer: &'info AccountInfo<'info>,
+    pub temp_wsol_account: &'info AccountInfo<'info>,
+    pub wsol_mint: &'info AccountInfo<'info>,
+}
+pub const DEPOSIT_SOL_ACCOUNTS_LEN: usize = 15;
+
+pub struct StakePoolWithdrawSolAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub stake_pool: &'info AccountInfo<'info>,
+    pub withdraw_authority: &'info AccountInfo<'info>,
+    pub reserve_stake: &'info AccountInfo<'info>,
+    pub manager_fee_account: &'info AccountInfo<'info>,
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+    pub clock: &'info AccountInfo<'info>,
+    pub stake_history: &'info AccountInfo<'info>,
+    pub stake_program: &'info AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+pub const WITHDRAW_SOL_ACCOUNTS_LEN: usize = 13;
+
+impl<'info> StakePoolDepositSolAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            stake_pool,
+            withdraw_authority,
+            reserve_stake,
+            manager_fee_account,
+            referrer_fee_account,
+            pool_mint,
+            system_program,
+            token_program,
+            payer,
+            temp_wsol_account,
+            wsol_mint,
+        ]: &[AccountInfo<'info>; DEPOSIT_SOL_ACCOUNTS_LEN] =
+            array_ref![accounts, offset, DEPOSIT_SOL_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            stake_pool,
+            withdraw_authority,
+            reserve_stake,
+            manager_fee_account,
+            referrer_fee_account,
+            pool_mint: InterfaceAccount::try_from(pool_mint)?,
+            system_program: Program::try_from(system_program)?,
+            token_program: Program::try_from(token_program)?,
+            payer,
+            temp_wsol_account,
+            wsol_mint,
+        })
+    }
+}
+
+impl<'info> StakePoolWithdrawSolAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            stake_pool,
+            withdraw_authority,
+            reserve_stake,
+            manager_fee_account,
+            pool_mint,
+            clock,
+            stake_history,
+            stake_program,
+            token_program,
+        ]: &[AccountInfo<'info>; WITHDRAW_SOL_ACCOUNTS_LEN] =
+            array_ref![accounts, offset, WITHDRAW_SOL_ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            stake_pool,
+            withdraw_authority,
+            reserve_stake,
+            manager_fee_account,
+            pool_mint: InterfaceAccount::try_from(pool_mint)?,
+            clock,
+            stake_history,
+            stake_program,
+            token_program: Program::try_from(token_program)?,
+        })
+    }
+}
+
+pub fn deposit_sol<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::SplStakePoolDepositSol amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + DEPOSIT_SOL_ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts =
+        StakePoolDepositSolAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &spl_stake_pool_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == wsol_program::id()
+            && swap_accounts.wsol_mint.key() == wsol_program::id()
+            && swap_accounts.swap_destination_token.mint == swap_accounts.pool_mint.key(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    // Unwrap amount_in wSOL into the swap authority, which funds the deposit
+    let authority_lamports = swap_accounts.swap_authority_pubkey.lamports();
+    let rent_paid = unwrap_wsol_to_authority(
+        swap_accounts.payer.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.temp_wsol_account.to_account_info(),
+        swap_accounts.wsol_mint.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        amount_in,
+    )?;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.push(DEPOSIT_SOL_INSTRUCTION);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.stake_pool.key(), false),
+        AccountMeta::new_readonly(swap_accounts.withdraw_authority.key(), false),
+        AccountMeta::new(swap_accounts.reserve_stake.key(), false),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new(swap_accounts.manager_fee_account.key(), false),
+        AccountMeta::new(swap_accounts.referrer_fee_account.key(), false),
+        AccountMeta::new(swap_accounts.pool_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.system_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.stake_pool.to_account_info(),
+        swap_accounts.withdraw_authority.to_account_info(),
+        swap_accounts.reserve_stake.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.manager_fee_account.to_account_info(),
+        swap_accounts.referrer_fee_account.to_account_info(),
+        swap_accounts.pool_mint.to_account_info(),
+        swap_accounts.system_program.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SolInProcessor {
+        authority: swap_accounts.swap_authority_pubkey.to_account_info(),
+        source_token_account: swap_accounts.swap_source_token.to_account_info(),
+        token_program: swap_accounts.token_program.to_account_info(),
+        payer: swap_accounts.payer.to_account_info(),
+        authority_lamports,
+        rent_paid,
+    };
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        DEPOSIT_SOL_ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+pub fn withdraw_sol<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::SplStakePoolWithdrawSol amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + WITHDRAW_SOL_ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts =
+        StakePoolWithdrawSolAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &spl_stake_pool_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.pool_mint.key()
+            && swap_accounts.swap_destination_token.mint == wsol_program::id(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.push(WITHDRAW_SOL_INSTRUCTION);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+
+    // lamports are paid to the swap authority and wrapped into the destination after the invoke
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.stake_pool.key(), false),
+        AccountMeta::new_readonly(swap_accounts.withdraw_authority.key(), false),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_accounts.reserve_stake.key(), false),
+        AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_accounts.manager_fee_account.key(), false),
+        AccountMeta::new(swap_accounts.pool_mint.key(), false),
+        AccountMeta::new_readonly(swap_accounts.clock.key(), false),
+        AccountMeta::new_readonly(swap_accounts.stake_history.key(), false),
+        AccountMeta::new_readonly(swap_accounts.stake_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.stake_pool.to_account_info(),
+        swap_accounts.withdraw_authority.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.reserve_stake.to_account_info(),
+        swap_accounts.manager_fee_account.to_account_info(),
+        swap_accounts.pool_mint.to_account_info(),
+        swap_accounts.clock.to_account_info(),
+        swap_accounts.stake_history.to_account_info(),
+        swap_accounts.stake_program.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &SolOutProcessor {
+        authority: swap_accounts.swap_authority_pubkey.to_account_info(),
+        destination_token_account: swap_accounts.swap_destination_token.to_account_info(),
+        token_program: swap_accounts.token_program.to_account_info(),
+        authority_lamports: swap_accounts.swap_authority_pubkey.lamports(),
+    };
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        WITHDRAW_SOL_ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_deposit_sol_instruction() {
+        let amount_in = 100u64;
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.push(DEPOSIT_SOL_INSTRUCTION);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+
+    #[test]
+    pub fn test_pack_withdraw_sol_instruction() {
+        let amount_in = 100u64;
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.push(WITHDRAW_SOL_INSTRUCTION);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
-use crate::{whirlpool_program, HopAccounts, SWAP_SELECTOR, SWAP_V2_SELECTOR};
+use crate::{
+    whirlpool_program, HopAccounts, SWAP_SELECTOR, SWAP_V2_SELECTOR, TWO_HOP_SWAP_V2_SELECTOR,
+};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -10,6 +12,12 @@ use super::common::DexProcessor;
 
 const ARGS_LEN: usize = 42;
 const ARGS_V2_LEN: usize = 43;
+const ARGS_V2_SUPPLEMENTAL_LEN: usize = ARGS_V2_LEN + 6;
+const ARGS_TWO_HOP_V2_LEN: usize = 60;
+// RemainingAccountsInfo slice type of Whirlpool supplemental tick arrays
+const ACCOUNTS_TYPE_SUPPLEMENTAL_TICK_ARRAYS: u8 = 6;
+const MIN_SQRT_PRICE: u128 = 4295048016;
+const MAX_SQRT_PRICE: u128 = 79226673515401279992447579055;
 
 pub struct WhirlpoolProcessor;
 impl DexProcessor for WhirlpoolProcessor {}
@@ -51,6 +59,38 @@ pub struct WhirlpoolV2Accounts<'info> {
     pub oracle: &'info AccountInfo<'info>,
 }
 pub const ACCOUNTS_V2_LEN: usize = 16;
+pub const MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN: usize = 3;
+pub const MAX_ACCOUNTS_V2_LEN: usize = ACCOUNTS_V2_LEN + MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN;
+
+pub struct WhirlpoolTwoHopAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub whirlpool_one: &'info AccountInfo<'info>,
+    pub whirlpool_two: &'info AccountInfo<'info>,
+    pub token_mint_input: InterfaceAccount<'info, Mint>,
+    pub token_mint_intermediate: InterfaceAccount<'info, Mint>,
+    pub token_mint_output: InterfaceAccount<'info, Mint>,
+    pub token_program_input: Interface<'info, TokenInterface>,
+    pub token_program_intermediate: Interface<'info, TokenInterface>,
+    pub token_program_output: Interface<'info, TokenInterface>,
+    pub token_vault_one_input: &'info AccountInfo<'info>,
+    pub token_vault_one_intermediate: &'info AccountInfo<'info>,
+    pub token_vault_two_intermediate: &'info AccountInfo<'info>,
+    pub token_vault_two_output: &'info AccountInfo<'info>,
+    pub tick_array_one0: &'info AccountInfo<'info>,
+    pub tick_array_one1: &'info AccountInfo<'info>,
+    pub tick_array_one2: &'info AccountInfo<'info>,
+    pub tick_array_two0: &'info AccountInfo<'info>,
+    pub tick_array_two1: &'info AccountInfo<'info>,
+    pub tick_array_two2: &'info AccountInfo<'info>,
+    pub oracle_one: &'info AccountInfo<'info>,
+    pub oracle_two: &'info AccountInfo<'info>,
+    pub memo_program: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_TWO_HOP_LEN: usize = 25;
 
 impl<'info> WhirlpoolAccounts<'info> {
     fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
@@ -126,6 +166,72 @@ impl<'info> WhirlpoolV2Accounts<'info> {
     }
 }
 
+impl<'info> WhirlpoolTwoHopAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            whirlpool_one,
+            whirlpool_two,
+            token_mint_input,
+            token_mint_intermediate,
+            token_mint_output,
+            token_program_input,
+            token_program_intermediate,
+            token_program_output,
+            token_vault_one_input,
+            token_vault_one_intermediate,
+            token_vault_two_intermediate,
+            token_vault_two_output,
+            tick_array_one0,
+            tick_array_one1,
+            tick_array_one2,
+            tick_array_two0,
+            tick_array_two1,
+            tick_array_two2,
+            oracle_one,
+            oracle_two,
+            memo_program,
+        ]: & [AccountInfo<'info>; ACCOUNTS_TWO_HOP_LEN] = array_ref![accounts, offset, ACCOUNTS_TWO_HOP_LEN];
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            whirlpool_one,
+            whirlpool_two,
+            token_mint_input: InterfaceAccount::try_from(token_mint_input)?,
+            token_mint_intermediate: InterfaceAccount::try_from(token_mint_intermediate)?,
+            token_mint_output: InterfaceAccount::try_from(token_mint_output)?,
+            token_program_input: Interface::try_from(token_program_input)?,
+            token_program_intermediate: Interface::try_from(token_program_intermediate)?,
+            token_program_output: Interface::try_from(token_program_output)?,
+            token_vault_one_input,
+            token_vault_one_intermediate,
+            token_vault_two_intermediate,
+            token_vault_two_output,
+            tick_array_one0,
+            tick_array_one1,
+            tick_array_one2,
+            tick_array_two0,
+            tick_array_two1,
+            tick_array_two2,
+            oracle_one,
+            oracle_two,
+            memo_program,
+        })
+    }
+}
+
+// token_mint_a of the whirlpool account
+fn get_token_mint_a(whirlpool: &AccountInfo) -> Result<Pubkey> {
+    let data = &whirlpool.try_borrow_data()?;
+    require!(data.len() >= 133, ErrorCode::InvalidPool);
+    Ok(Pubkey::new_from_array(*array_ref![data, 101, 32]))
+}
+
 pub fn swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -247,6 +353,7 @@ pub fn swap<'a>(
     Ok(amount_out)
 }
 
+// Supplemental tick arrays are appended after the oracle account
 pub fn swap_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
@@ -254,14 +361,23 @@ pub fn swap_v2<'a>(
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
+    accounts_len: Option<usize>,
 ) -> Result<u64> {
     msg!(
         "Dex::WhirlpoolV2 amount_in: {}, offset: {}",
         amount_in,
         offset
     );
+    let accounts_len = accounts_len.unwrap_or(ACCOUNTS_V2_LEN);
+    let supplemental_len = accounts_len
+        .checked_sub(ACCOUNTS_V2_LEN)
+        .ok_or(ErrorCode::InvalidAccountsLength)?;
+    require!(
+        supplemental_len <= MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
     require!(
-        remaining_accounts.len() >= *offset + ACCOUNTS_V2_LEN,
+        remaining_accounts.len() >= *offset + accounts_len,
         ErrorCode::InvalidAccountsLength
     );
     let mut swap_accounts = WhirlpoolV2Accounts::parse_accounts(remaining_accounts, *offset)?;
@@ -310,16 +426,26 @@ pub fn swap_v2<'a>(
         )
     };
 
-    let mut data = Vec::with_capacity(ARGS_V2_LEN);
+    let mut data = Vec::with_capacity(ARGS_V2_SUPPLEMENTAL_LEN);
     data.extend_from_slice(SWAP_V2_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
     data.extend_from_slice(&other_amount_threshold.to_le_bytes());
     data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
     data.extend_from_slice(&(amount_specified_is_input as u8).to_le_bytes());
     data.extend_from_slice(&(a_to_b as u8).to_le_bytes());
-    data.extend_from_slice(&(0u8).to_le_bytes());
+    if supplemental_len == 0 {
+        data.extend_from_slice(&(0u8).to_le_bytes());
+    } else {
+        // Some(RemainingAccountsInfo { slices: [SupplementalTickArrays] })
+        data.extend_from_slice(&(1u8).to_le_bytes());
+        data.extend_from_slice(&(1u32).to_le_bytes());
+        data.extend_from_slice(&ACCOUNTS_TYPE_SUPPLEMENTAL_TICK_ARRAYS.to_le_bytes());
+        data.extend_from_slice(&(supplemental_len as u8).to_le_bytes());
+    }
+    let supplemental_tick_arrays =
+        &remaining_accounts[*offset + ACCOUNTS_V2_LEN..*offset + accounts_len];
 
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new_readonly(swap_accounts.token_program_a.key(), false),
         AccountMeta::new_readonly(swap_accounts.token_program_b.key(), false),
         AccountMeta::new_readonly(swap_accounts.memo_program.key(), false),
@@ -336,8 +462,13 @@ pub fn swap_v2<'a>(
         AccountMeta::new(swap_accounts.tick_array2.key(), false),
         AccountMeta::new(swap_accounts.oracle.key(), false),
     ];
+    accounts.extend(
+        supplemental_tick_arrays
+            .iter()
+            .map(|tick_array| AccountMeta::new(tick_array.key(), false)),
+    );
 
-    let account_infos = vec![
+    let mut account_infos = vec![
         swap_accounts.token_program_a.to_account_info(),
         swap_accounts.token_program_b.to_account_info(),
         swap_accounts.memo_program.to_account_info(),
@@ -354,6 +485,150 @@ pub fn swap_v2<'a>(
         swap_accounts.tick_array2.to_account_info(),
         swap_accounts.oracle.to_account_info(),
     ];
+    account_infos.extend(supplemental_tick_arrays.iter().cloned());
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &WhirlpoolProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        accounts_len,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+pub fn two_hop_swap_v2<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!(
+        "Dex::WhirlpoolTwoHop amount_in: {}, offset: {}",
+        amount_in,
+        offset
+    );
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_TWO_HOP_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+    let mut swap_accounts = WhirlpoolTwoHopAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &whirlpool_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority, the two whirlpools count as one hop
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.token_mint_input.key()
+            && swap_accounts.swap_destination_token.mint == swap_accounts.token_mint_output.key(),
+        ErrorCode::InvalidTokenMint
+    );
+
+    let a_to_b_one =
+        get_token_mint_a(swap_accounts.whirlpool_one)? == swap_accounts.token_mint_input.key();
+    let a_to_b_two = get_token_mint_a(swap_accounts.whirlpool_two)?
+        == swap_accounts.token_mint_intermediate.key();
+    let sqrt_price_limit_one = if a_to_b_one {
+        MIN_SQRT_PRICE
+    } else {
+        MAX_SQRT_PRICE
+    };
+    let sqrt_price_limit_two = if a_to_b_two {
+        MIN_SQRT_PRICE
+    } else {
+        MAX_SQRT_PRICE
+    };
+    let amount_specified_is_input = true;
+    let other_amount_threshold = 1u64;
+
+    let mut data = Vec::with_capacity(ARGS_TWO_HOP_V2_LEN);
+    data.extend_from_slice(TWO_HOP_SWAP_V2_SELECTOR);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&other_amount_threshold.to_le_bytes());
+    data.extend_from_slice(&(amount_specified_is_input as u8).to_le_bytes());
+    data.extend_from_slice(&(a_to_b_one as u8).to_le_bytes());
+    data.extend_from_slice(&(a_to_b_two as u8).to_le_bytes());
+    data.extend_from_slice(&sqrt_price_limit_one.to_le_bytes());
+    data.extend_from_slice(&sqrt_price_limit_two.to_le_bytes());
+    data.extend_from_slice(&(0u8).to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.whirlpool_one.key(), false),
+        AccountMeta::new(swap_accounts.whirlpool_two.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_mint_input.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_mint_intermediate.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_mint_output.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program_input.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program_intermediate.key(), false),
+        AccountMeta::new_readonly(swap_accounts.token_program_output.key(), false),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_accounts.token_vault_one_input.key(), false),
+        AccountMeta::new(swap_accounts.token_vault_one_intermediate.key(), false),
+        AccountMeta::new(swap_accounts.token_vault_two_intermediate.key(), false),
+        AccountMeta::new(swap_accounts.token_vault_two_output.key(), false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new(swap_accounts.tick_array_one0.key(), false),
+        AccountMeta::new(swap_accounts.tick_array_one1.key(), false),
+        AccountMeta::new(swap_accounts.tick_array_one2.key(), false),
+        AccountMeta::new(swap_accounts.tick_array_two0.key(), false),
+        AccountMeta::new(swap_accounts.tick_array_two1.key(), false),
+        AccountMeta::new(swap_accounts.tick_array_two2.key(), false),
+        AccountMeta::new(swap_accounts.oracle_one.key(), false),
+        AccountMeta::new(swap_accounts.oracle_two.key(), false),
+        AccountMeta::new_readonly(swap_accounts.memo_program.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.whirlpool_one.to_account_info(),
+        swap_accounts.whirlpool_two.to_account_info(),
+        swap_accounts.token_mint_input.to_account_info(),
+        swap_accounts.token_mint_intermediate.to_account_info(),
+        swap_accounts.token_mint_output.to_account_info(),
+        swap_accounts.token_program_input.to_account_info(),
+        swap_accounts.token_program_intermediate.to_account_info(),
+        swap_accounts.token_program_output.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.token_vault_one_input.to_account_info(),
+        swap_accounts.token_vault_one_intermediate.to_account_info(),
+        swap_accounts.token_vault_two_intermediate.to_account_info(),
+        swap_accounts.token_vault_two_output.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.tick_array_one0.to_account_info(),
+        swap_accounts.tick_array_one1.to_account_info(),
+        swap_accounts.tick_array_one2.to_account_info(),
+        swap_accounts.tick_array_two0.to_account_info(),
+        swap_accounts.tick_array_two1.to_account_info(),
+        swap_accounts.tick_array_two2.to_account_info(),
+        swap_accounts.oracle_one.to_account_info(),
+        swap_accounts.oracle_two.to_account_info(),
+        swap_accounts.memo_program.to_account_info(),
+    ];
 
     let instruction = Instruction {
         program_id: swap_accounts.dex_program_id.key(),
@@ -371,7 +646,7 @@ pub fn swap_v2<'a>(
         instruction,
         hop,
         offset,
-        ACCOUNTS_V2_LEN,
+        ACCOUNTS_TWO_HOP_LEN,
         proxy_swap,
     )?;
     Ok(amount_out)
@@ -400,4 +675,52 @@ mod tests {
         msg!("data.len: {}", data.len());
         assert!(data.len() == ARGS_LEN);
     }
+
+    #[test]
+    pub fn test_pack_two_hop_swap_v2_instruction() {
+        let amount_in = 100u64;
+        let amount_specified_is_input = true;
+        let other_amount_threshold = 1u64;
+        let a_to_b_one = true;
+        let a_to_b_two = false;
+
+        let mut data = Vec::with_capacity(ARGS_TWO_HOP_V2_LEN);
+        data.extend_from_slice(TWO_HOP_SWAP_V2_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&other_amount_threshold.to_le_bytes());
+        data.extend_from_slice(&(amount_specified_is_input as u8).to_le_bytes());
+        data.extend_from_slice(&(a_to_b_one as u8).to_le_bytes());
+        data.extend_from_slice(&(a_to_b_two as u8).to_le_bytes());
+        data.extend_from_slice(&MIN_SQRT_PRICE.to_le_bytes());
+        data.extend_from_slice(&MAX_SQRT_PRICE.to_le_bytes());
+        data.extend_from_slice(&(0u8).to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_TWO_HOP_V2_LEN);
+    }
+
+    #[test]
+    pub fn test_pack_swap_v2_supplemental_instruction() {
+        let amount_in = 100u64;
+        let amount_specified_is_input = true;
+        let other_amount_threshold = 1u64;
+        let a_to_b = true;
+        let sqrt_price_limit = MIN_SQRT_PRICE as i128;
+        let supplemental_len = 2u8;
+
+        let mut data = Vec::with_capacity(ARGS_V2_SUPPLEMENTAL_LEN);
+        data.extend_from_slice(SWAP_V2_SELECTOR);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&other_amount_threshold.to_le_bytes());
+        data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
+        data.extend_from_slice(&(amount_specified_is_input as u8).to_le_bytes());
+        data.extend_from_slice(&(a_to_b as u8).to_le_bytes());
+        data.extend_from_slice(&(1u8).to_le_bytes());
+        data.extend_from_slice(&(1u32).to_le_bytes());
+        data.extend_from_slice(&ACCOUNTS_TYPE_SUPPLEMENTAL_TICK_ARRAYS.to_le_bytes());
+        data.extend_from_slice(&supplemental_len.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_V2_SUPPLEMENTAL_LEN);
+    }
 }
@@ -0,0 +1,173 @@
+use crate::adapters::common::{before_check, invoke_process};
+use crate::error::ErrorCode;
+use crate::{zerofi_program, HopAccounts};
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
+use anchor_spl::token_interface::TokenAccount;
+use arrayref::array_ref;
+
+use super::common::DexProcessor;
+
+const ARGS_LEN: usize = 17;
+const SWAP_INSTRUCTION: u8 = 6;
+
+pub struct ZeroFiProcessor;
+impl DexProcessor for ZeroFiProcessor {}
+
+pub struct ZeroFiAccounts<'info> {
+    pub dex_program_id: &'info AccountInfo<'info>,
+    pub swap_authority_pubkey: &'info AccountInfo<'info>,
+    pub swap_source_token: InterfaceAccount<'info, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub pair: &'info AccountInfo<'info>,
+    pub vault_info_in: &'info AccountInfo<'info>,
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+    pub vault_info_out: &'info AccountInfo<'info>,
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'info AccountInfo<'info>,
+    pub sysvar_instructions: &'info AccountInfo<'info>,
+}
+pub const ACCOUNTS_LEN: usize = 11;
+
+impl<'info> ZeroFiAccounts<'info> {
+    fn parse_accounts(accounts: &'info [AccountInfo<'info>], offset: usize) -> Result<Self> {
+        let [
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token,
+            swap_destination_token,
+            pair,
+            vault_info_in,
+            vault_in,
+            vault_info_out,
+            vault_out,
+            token_program,
+            sysvar_instructions,
+        ]: &[AccountInfo<'info>; ACCOUNTS_LEN] = array_ref![accounts, offset, ACCOUNTS_LEN];
+
+        Ok(Self {
+            dex_program_id,
+            swap_authority_pubkey,
+            swap_source_token: InterfaceAccount::try_from(swap_source_token)?,
+            swap_destination_token: InterfaceAccount::try_from(swap_destination_token)?,
+            pair,
+            vault_info_in,
+            vault_in: InterfaceAccount::try_from(vault_in)?,
+            vault_info_out,
+            vault_out: InterfaceAccount::try_from(vault_out)?,
+            token_program,
+            sysvar_instructions,
+        })
+    }
+}
+
+pub fn swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+) -> Result<u64> {
+    msg!("Dex::ZeroFi amount_in: {}, offset: {}", amount_in, offset);
+    require!(
+        remaining_accounts.len() >= *offset + ACCOUNTS_LEN,
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut swap_accounts = ZeroFiAccounts::parse_accounts(remaining_accounts, *offset)?;
+    if swap_accounts.dex_program_id.key != &zerofi_program::id() {
+        return Err(ErrorCode::InvalidProgramId.into());
+    }
+
+    // check hop accounts & swap authority
+    let swap_source_token = swap_accounts.swap_source_token.key();
+    let swap_destination_token = swap_accounts.swap_destination_token.key();
+    before_check(
+        swap_accounts.swap_authority_pubkey,
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        hop,
+        proxy_swap,
+    )?;
+
+    // the vaults are passed in swap direction
+    require!(
+        swap_accounts.swap_source_token.mint == swap_accounts.vault_in.mint
+            && swap_accounts.swap_destination_token.mint == swap_accounts.vault_out.mint,
+        ErrorCode::InvalidTokenMint
+    );
+    let minimum_amount_out = 0u64;
+
+    let mut data = Vec::with_capacity(ARGS_LEN);
+    data.push(SWAP_INSTRUCTION);
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+    let accounts = vec![
+        AccountMeta::new(swap_accounts.pair.key(), false),
+        AccountMeta::new(swap_accounts.vault_info_in.key(), false),
+        AccountMeta::new(swap_accounts.vault_in.key(), false),
+        AccountMeta::new(swap_accounts.vault_info_out.key(), false),
+        AccountMeta::new(swap_accounts.vault_out.key(), false),
+        AccountMeta::new(swap_source_token, false),
+        AccountMeta::new(swap_destination_token, false),
+        AccountMeta::new_readonly(swap_accounts.swap_authority_pubkey.key(), true),
+        AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
+        AccountMeta::new_readonly(swap_accounts.sysvar_instructions.key(), false),
+    ];
+
+    let account_infos = vec![
+        swap_accounts.pair.to_account_info(),
+        swap_accounts.vault_info_in.to_account_info(),
+        swap_accounts.vault_in.to_account_info(),
+        swap_accounts.vault_info_out.to_account_info(),
+        swap_accounts.vault_out.to_account_info(),
+        swap_accounts.swap_source_token.to_account_info(),
+        swap_accounts.swap_destination_token.to_account_info(),
+        swap_accounts.swap_authority_pubkey.to_account_info(),
+        swap_accounts.token_program.to_account_info(),
+        swap_accounts.sysvar_instructions.to_account_info(),
+    ];
+
+    let instruction = Instruction {
+        program_id: swap_accounts.dex_program_id.key(),
+        accounts,
+        data,
+    };
+
+    let dex_processor = &ZeroFiProcessor;
+    let amount_out = invoke_process(
+        dex_processor,
+        &account_infos,
+        swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+    )?;
+    Ok(amount_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pack_swap_instruction() {
+        let amount_in = 100u64;
+        let minimum_amount_out = 0u64;
+
+        let mut data = Vec::with_capacity(ARGS_LEN);
+        data.push(SWAP_INSTRUCTION);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+        msg!("data.len: {}", data.len());
+        assert!(data.len() == ARGS_LEN);
+    }
+}
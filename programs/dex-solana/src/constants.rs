@@ -3,6 +3,9 @@ use anchor_lang::prelude::*;
 #[constant]
 pub const SEED_SA: &[u8] = b"okx_sa";
 pub const BUMP_SA: u8 = 251;
+pub const SEED_TEMP_WSOL: &[u8] = b"okx_temp_wsol";
+pub const SEED_TO_SWAP_AUTHORITY: &[u8] = b"okx_to_swap_authority"; // PDA of the bridge program
+pub const SEED_TO_SWAP_ORDER: &[u8] = b"okx_to_swap_order";
 pub const COMMISSION_RATE_LIMIT: u16 = 300;
 pub const COMMISSION_DENOMINATOR: u64 = 10000;
 pub const MAX_HOPS: usize = 3;
@@ -11,12 +14,19 @@ pub const TOTAL_WEIGHT: u8 = 100;
 pub const SWAP_SELECTOR: &[u8; 8] = &[248, 198, 158, 145, 225, 117, 135, 200];
 pub const CPSWAP_SELECTOR: &[u8; 8] = &[143, 190, 90, 218, 196, 30, 51, 222];
 pub const SWAP_V2_SELECTOR: &[u8; 8] = &[43, 4, 237, 11, 26, 201, 30, 98];
+pub const SWAP2_SELECTOR: &[u8; 8] = &[65, 75, 63, 76, 235, 91, 91, 136];
+pub const TWO_HOP_SWAP_V2_SELECTOR: &[u8; 8] = &[186, 143, 209, 29, 254, 2, 194, 117];
 pub const PLACE_TAKE_ORDER_SELECTOR: &[u8; 8] = &[3, 44, 71, 3, 26, 199, 203, 85];
 pub const BRIDGE_TO_LOG_SELECTOR: &[u8; 8] = &[212, 189, 176, 218, 196, 135, 64, 122];
 pub const ZERO_ADDRESS: Pubkey = Pubkey::new_from_array([0u8; 32]);
 
 pub const PUMPFUN_BUY_SELECTOR: &[u8; 8] = &[102, 6, 61, 18, 1, 218, 235, 234];
 pub const PUMPFUN_SELL_SELECTOR: &[u8; 8] = &[51, 230, 133, 164, 1, 127, 131, 173];
+pub const BUY_EXACT_IN_SELECTOR: &[u8; 8] = &[250, 234, 13, 123, 213, 156, 19, 236];
+pub const SELL_EXACT_IN_SELECTOR: &[u8; 8] = &[149, 39, 222, 155, 211, 124, 152, 26];
+pub const SWAP_EXACT_IN_SELECTOR: &[u8; 8] = &[104, 104, 131, 86, 161, 189, 180, 216];
+pub const MARINADE_DEPOSIT_SELECTOR: &[u8; 8] = &[242, 35, 198, 137, 82, 225, 242, 182];
+pub const MARINADE_LIQUID_UNSTAKE_SELECTOR: &[u8; 8] = &[30, 30, 119, 240, 191, 227, 12, 16];
 
 pub mod authority_pda {
     use anchor_lang::declare_id;
@@ -59,6 +69,11 @@ pub mod raydium_cpmm_program {
     declare_id!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
 }
 
+pub mod raydium_launchlab_program {
+    use anchor_lang::declare_id;
+    declare_id!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj");
+}
+
 pub mod aldrin_v1_program {
     use anchor_lang::declare_id;
     declare_id!("AMM55ShdkoGRB5jVYPjWziwk8m5MpwyDgsMWHaMSQWH6");
@@ -84,6 +99,11 @@ pub mod meteora_dlmm_program {
     declare_id!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
 }
 
+pub mod meteora_damm_v2_program {
+    use anchor_lang::declare_id;
+    declare_id!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+}
+
 pub mod lifinity_v1pool_program {
     use anchor_lang::declare_id;
     declare_id!("EewxydAPCCVuNEyrVN68PuSYdQ7wKn27V9Gjeoi8dy3S");
@@ -109,17 +129,87 @@ pub mod phoenix_program {
     declare_id!("PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY");
 }
 
+pub mod manifest_program {
+    use anchor_lang::declare_id;
+    declare_id!("MNFSTqtC93rEfYHB6hF82sKdZpUDFWkViLByLd1k1Ms");
+}
+
+pub mod invariant_program {
+    use anchor_lang::declare_id;
+    declare_id!("HyaB3W9q6XdA5xwpU4XnSZV94htfmbmqJXZcEbRaJutt");
+}
+
+pub mod crema_program {
+    use anchor_lang::declare_id;
+    declare_id!("CLMM9tUoggJu2wagPkkqs9eFG4BWhVBZWkP1qv3Sp7tR");
+}
+
+pub mod saros_dlmm_program {
+    use anchor_lang::declare_id;
+    declare_id!("1qbkdrr3z4ryLA7pZykqxvxWPoeifcVKo6ZG9CfkvVE");
+}
+
+pub mod stabble_stable_program {
+    use anchor_lang::declare_id;
+    declare_id!("swapNyd8XiQwJ6ianp9snpu4brUqFxadzvHebnAXjJZ");
+}
+
+pub mod stabble_weighted_program {
+    use anchor_lang::declare_id;
+    declare_id!("swapFpHZwjELNnjvThjajtiVmkz3yPQEHjLtka2fwHW");
+}
+
+pub mod perena_program {
+    use anchor_lang::declare_id;
+    declare_id!("NUMERUNsFCP3kuNmWZuXtm1AaQCPj9uw6Guv2Ekoi5P");
+}
+
 pub mod obric_v2_program {
     use anchor_lang::declare_id;
     declare_id!("obriQD1zbpyLz95G5n7nJe6a4DPjpFwa5XYPoNm113y");
 }
 
+pub mod solfi_program {
+    use anchor_lang::declare_id;
+    declare_id!("SoLFiHG9TfgtdUXUjWAxi3LtvYuFyDLVhBWxdMZxyCe");
+}
+
+pub mod zerofi_program {
+    use anchor_lang::declare_id;
+    declare_id!("ZERor4xhbUycZ6gb9ntrhqscUcZmAbQDjEAtCf4hbZY");
+}
+
+pub mod gamma_program {
+    use anchor_lang::declare_id;
+    declare_id!("GAMMA7meSFWaBXF25oSUgmGRwaW6sCMFLmBNiMSdbHVT");
+}
+
 pub mod sanctum_program {
     use anchor_lang::declare_id;
     declare_id!("5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx");
 }
 
+pub mod sanctum_router_program {
+    use anchor_lang::declare_id;
+    declare_id!("stkitrT1Uoy18Dk1fTrgPw8W6MVzoCfYoAFT4MLsmhq");
+}
+
+pub mod spl_stake_pool_program {
+    use anchor_lang::declare_id;
+    declare_id!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+}
+
+pub mod marinade_program {
+    use anchor_lang::declare_id;
+    declare_id!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
+}
+
 pub mod pumpfun_program {
     use anchor_lang::declare_id;
     declare_id!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
 }
+
+pub mod pumpswap_program {
+    use anchor_lang::declare_id;
+    declare_id!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+}
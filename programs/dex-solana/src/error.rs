@@ -85,4 +85,34 @@ pub enum ErrorCode {
 
     #[msg("Invalid sanctum swap accounts")]
     InvalidSanctumSwapAccounts,
+
+    #[msg("Invalid fee route")]
+    InvalidFeeRoute,
+
+    #[msg("Invalid bridge caller")]
+    InvalidBridgeCaller,
+
+    #[msg("Invalid bridge to address")]
+    InvalidBridgeToAddress,
+
+    #[msg("Invalid temp wsol account")]
+    InvalidTempWsolAccount,
+
+    #[msg("Dexes and accounts lens must have the same length")]
+    DexesAndAccountsLensMustHaveTheSameLength,
+
+    #[msg("Dexes and dex params must have the same length")]
+    DexesAndDexParamsMustHaveTheSameLength,
+
+    #[msg("Invalid dex params")]
+    InvalidDexParams,
+
+    #[msg("Oracle is stale")]
+    OracleStale,
+
+    #[msg("Amount in remainder can not be refunded")]
+    AmountInRemainderNotRefunded,
+
+    #[msg("Optional dexes require accounts lens")]
+    OptionalDexRequiresAccountsLens,
 }
@@ -0,0 +1,142 @@
+use crate::error::ErrorCode;
+use crate::{
+    swap_process, CommissionFeeRouteSwapArgs, SwapArgsV2, COMMISSION_DENOMINATOR,
+    COMMISSION_RATE_LIMIT,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CommissionFeeRouteAccounts<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = payer,
+    )]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = destination_mint,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub source_mint: InterfaceAccount<'info, Mint>,
+
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = fee_mint,
+    )]
+    pub commission_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn commission_fee_route_swap_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, CommissionFeeRouteAccounts<'a>>,
+    args: CommissionFeeRouteSwapArgs,
+    order_id: u64,
+) -> Result<u64> {
+    // CHECK: CommissionFeeRouteSwapArgs
+    require!(
+        args.commission_rate > 0 && args.commission_rate <= COMMISSION_RATE_LIMIT,
+        ErrorCode::InvalidCommissionRate
+    );
+    require!(!args.fee_route.is_empty(), ErrorCode::InvalidFeeRoute);
+    let fee_offset = args.fee_offset as usize;
+    require!(
+        ctx.remaining_accounts.len() > fee_offset,
+        ErrorCode::InvalidAccountsLength
+    );
+    if !args.commission_direction {
+        // The commission is swapped out of destination_token_account, so the payer must own it
+        require!(
+            ctx.accounts.destination_token_account.owner == ctx.accounts.payer.key(),
+            ErrorCode::InvalidDestinationTokenAccount
+        );
+    }
+
+    let mut commission_amount: u64 = 0;
+    if args.commission_direction {
+        // Commission for fromToken
+        commission_amount = args
+            .amount_in
+            .checked_mul(args.commission_rate as u64)
+            .ok_or(ErrorCode::CalculationError)?
+            .checked_div(COMMISSION_DENOMINATOR - args.commission_rate as u64)
+            .ok_or(ErrorCode::CalculationError)?;
+    }
+
+    // 1.Smart swap
+    let swap_args = SwapArgsV2 {
+        amount_in: args.amount_in,
+        expect_amount_out: args.expect_amount_out,
+        min_return: args.min_return,
+        amounts: args.amounts,
+        routes: args.routes,
+    };
+    let amount_out = swap_process(
+        &mut ctx.accounts.source_token_account,
+        &mut ctx.accounts.destination_token_account,
+        &ctx.accounts.source_mint,
+        &ctx.accounts.destination_mint,
+        &ctx.remaining_accounts[..fee_offset],
+        swap_args,
+        order_id,
+        false,
+    )?;
+
+    // Commission for toToken
+    if !args.commission_direction {
+        commission_amount = amount_out
+            .checked_mul(args.commission_rate as u64)
+            .ok_or(ErrorCode::CalculationError)?
+            .checked_div(COMMISSION_DENOMINATOR)
+            .ok_or(ErrorCode::CalculationError)?;
+    }
+    msg!(
+        "commission_direction: {:?}, commission_amount: {:?}",
+        args.commission_direction,
+        commission_amount
+    );
+    if commission_amount == 0 {
+        return Ok(amount_out);
+    }
+
+    // 2.Swap commission_amount into fee_mint and pay it to commission_token_account
+    let fee_swap_args = SwapArgsV2 {
+        amount_in: commission_amount,
+        expect_amount_out: args.fee_min_return,
+        min_return: args.fee_min_return,
+        amounts: vec![commission_amount],
+        routes: vec![args.fee_route],
+    };
+    let (commission_source_account, commission_source_mint) = if args.commission_direction {
+        (
+            &mut ctx.accounts.source_token_account,
+            &ctx.accounts.source_mint,
+        )
+    } else {
+        (
+            &mut ctx.accounts.destination_token_account,
+            &ctx.accounts.destination_mint,
+        )
+    };
+    let fee_amount_out = swap_process(
+        commission_source_account,
+        &mut ctx.accounts.commission_token_account,
+        commission_source_mint,
+        &ctx.accounts.fee_mint,
+        &ctx.remaining_accounts[fee_offset..],
+        fee_swap_args,
+        0,
+        false,
+    )?;
+    msg!("fee_amount_out: {:?}", fee_amount_out);
+
+    Ok(amount_out)
+}
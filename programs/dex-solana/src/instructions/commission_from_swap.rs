@@ -1,8 +1,8 @@
 use crate::error::ErrorCode;
-use crate::instructions::from_swap::cpi_bridge_to_log;
+use crate::instructions::from_swap::{cpi_bridge_to_log, BridgeToLogAccounts};
 use crate::utils::token::{transfer_sol_from_user, transfer_token_from_user};
 use crate::{
-    swap_process, wsol_program, BridgeToArgs, SwapArgs, COMMISSION_DENOMINATOR,
+    swap_process, wsol_program, BridgeToArgs, SwapArgsV2, COMMISSION_DENOMINATOR,
     COMMISSION_RATE_LIMIT,
 };
 use anchor_lang::prelude::*;
@@ -51,7 +51,7 @@ pub struct CommissionSOLFromSwapAccounts<'info> {
 
 pub fn commission_sol_from_swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionSOLFromSwapAccounts<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     commission_rate: u16,
     bridge_to_args: BridgeToArgs,
     offset: u8,
@@ -102,15 +102,17 @@ pub fn commission_sol_from_swap_handler<'a>(
         amount_out,
         offset,
         len,
-        &ctx.accounts.bridge_program,
-        &ctx.accounts.payer,
-        &ctx.accounts.destination_token_account,
-        &ctx.accounts.destination_mint,
-        &ctx.accounts.associated_token_program,
-        &ctx.accounts.token_program,
-        &ctx.accounts.token_2022_program,
-        &ctx.accounts.system_program,
-        &ctx.remaining_accounts,
+        BridgeToLogAccounts {
+            bridge_program: &ctx.accounts.bridge_program,
+            payer: &ctx.accounts.payer,
+            destination_token_account: &ctx.accounts.destination_token_account,
+            destination_mint: &ctx.accounts.destination_mint,
+            associated_token_program: &ctx.accounts.associated_token_program,
+            token_program: &ctx.accounts.token_program,
+            token_2022_program: &ctx.accounts.token_2022_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        ctx.remaining_accounts,
     )?;
 
     Ok(())
@@ -157,7 +159,7 @@ pub struct CommissionSPLFromSwapAccounts<'info> {
 
 pub fn commission_spl_from_swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionSPLFromSwapAccounts<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     commission_rate: u16,
     bridge_to_args: BridgeToArgs,
     offset: u8,
@@ -218,15 +220,17 @@ pub fn commission_spl_from_swap_handler<'a>(
         amount_out,
         offset,
         len,
-        &ctx.accounts.bridge_program,
-        &ctx.accounts.payer,
-        &ctx.accounts.destination_token_account,
-        &ctx.accounts.destination_mint,
-        &ctx.accounts.associated_token_program,
-        &ctx.accounts.token_program,
-        &ctx.accounts.token_2022_program,
-        &ctx.accounts.system_program,
-        &ctx.remaining_accounts,
+        BridgeToLogAccounts {
+            bridge_program: &ctx.accounts.bridge_program,
+            payer: &ctx.accounts.payer,
+            destination_token_account: &ctx.accounts.destination_token_account,
+            destination_mint: &ctx.accounts.destination_mint,
+            associated_token_program: &ctx.accounts.associated_token_program,
+            token_program: &ctx.accounts.token_program,
+            token_2022_program: &ctx.accounts.token_2022_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        ctx.remaining_accounts,
     )?;
 
     Ok(())
@@ -0,0 +1,147 @@
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::utils::token::transfer_token_from_user;
+use crate::{proxy_from_swap_process, BridgeToArgs, SwapArgsV2};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct CommissionProxyFromSwapAccounts<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = payer,
+        token::token_program = source_token_program,
+    )]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub source_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub destination_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: sa_authority
+    #[account(
+        seeds = [
+            SEED_SA,
+        ],
+        bump = BUMP_SA,
+    )]
+    pub sa_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = source_mint,
+        associated_token::authority = sa_authority,
+        associated_token::token_program = source_token_program,
+    )]
+    pub source_token_sa: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = destination_mint,
+        associated_token::authority = sa_authority,
+        associated_token::token_program = destination_token_program,
+    )]
+    pub destination_token_sa: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The swap output is moved here before the bridge takes it, the SA authority never signs for the bridge
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = destination_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = destination_token_program,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::token_program = source_token_program,
+    )]
+    pub commission_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: bridge_program
+    #[account(address = crate::okx_bridge_program::id())]
+    pub bridge_program: AccountInfo<'info>,
+
+    pub source_token_program: Interface<'info, TokenInterface>,
+    pub destination_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn commission_proxy_from_swap_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, CommissionProxyFromSwapAccounts<'a>>,
+    args: SwapArgsV2,
+    commission_rate: u16,
+    bridge_to_args: BridgeToArgs,
+    offset: u8,
+    len: u8,
+) -> Result<()> {
+    require!(
+        commission_rate > 0 && commission_rate <= COMMISSION_RATE_LIMIT,
+        ErrorCode::InvalidCommissionRate
+    );
+
+    let commission_amount = args
+        .amount_in
+        .checked_mul(commission_rate as u64)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(COMMISSION_DENOMINATOR - commission_rate as u64)
+        .ok_or(ErrorCode::CalculationError)?;
+
+    proxy_from_swap_process(
+        &ctx.accounts.payer,
+        &ctx.accounts.sa_authority,
+        &mut ctx.accounts.source_token_account,
+        &mut ctx.accounts.source_token_sa,
+        &mut ctx.accounts.destination_token_sa,
+        &ctx.accounts.destination_token_account,
+        &ctx.accounts.source_mint,
+        &ctx.accounts.destination_mint,
+        &ctx.accounts.source_token_program,
+        &ctx.accounts.destination_token_program,
+        &ctx.accounts.bridge_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_2022_program,
+        &ctx.accounts.system_program,
+        ctx.remaining_accounts,
+        args,
+        bridge_to_args,
+        offset,
+        len,
+    )?;
+
+    // Transfer commission_amount
+    transfer_token_from_user(
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.source_token_account.to_account_info(),
+        ctx.accounts.commission_token_account.to_account_info(),
+        ctx.accounts.source_mint.to_account_info(),
+        ctx.accounts.source_token_program.to_account_info(),
+        commission_amount,
+        ctx.accounts.source_mint.decimals,
+    )?;
+    msg!(
+        "commission_direction: {:?}, commission_amount: {:?}",
+        true,
+        commission_amount
+    );
+
+    Ok(())
+}
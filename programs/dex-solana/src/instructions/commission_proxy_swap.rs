@@ -1,7 +1,7 @@
 use crate::constants::*;
 use crate::error::ErrorCode;
 use crate::utils::token::{transfer_sol_from_user, transfer_token_from_user};
-use crate::{proxy_swap_process, SwapArgs, COMMISSION_DENOMINATOR, COMMISSION_RATE_LIMIT};
+use crate::{proxy_swap_process, SwapArgsV2, COMMISSION_DENOMINATOR, COMMISSION_RATE_LIMIT};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -69,7 +69,7 @@ pub struct CommissionSOLProxySwapAccounts<'info> {
 
 pub fn commission_sol_proxy_swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionSOLProxySwapAccounts<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     commission_rate: u16,
     commission_direction: bool,
     order_id: u64,
@@ -206,7 +206,7 @@ pub struct CommissionSPLProxySwapAccounts<'info> {
 
 pub fn commission_spl_proxy_swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionSPLProxySwapAccounts<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     commission_rate: u16,
     commission_direction: bool,
     order_id: u64,
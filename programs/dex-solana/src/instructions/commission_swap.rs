@@ -1,7 +1,7 @@
 use crate::error::ErrorCode;
 use crate::utils::token::{transfer_sol_from_user, transfer_token_from_user};
 use crate::{
-    swap_process, wsol_program, CommissionSwapArgs, SwapArgs, COMMISSION_DENOMINATOR,
+    swap_process, wsol_program, CommissionSwapArgs, SwapArgs, SwapArgsV2, COMMISSION_DENOMINATOR,
     COMMISSION_RATE_LIMIT,
 };
 use anchor_lang::prelude::*;
@@ -68,13 +68,14 @@ pub fn commission_sol_swap_handler<'a>(
         );
     }
 
-    let swap_args = SwapArgs {
+    let swap_args: SwapArgsV2 = SwapArgs {
         amount_in: args.amount_in,
         expect_amount_out: args.expect_amount_out,
         min_return: args.min_return,
         amounts: args.amounts,
         routes: args.routes,
-    };
+    }
+    .into();
     let amount_out = swap_process(
         &mut ctx.accounts.source_token_account,
         &mut ctx.accounts.destination_token_account,
@@ -173,13 +174,14 @@ pub fn commission_spl_swap_handler<'a>(
         );
     }
 
-    let swap_args = SwapArgs {
+    let swap_args: SwapArgsV2 = SwapArgs {
         amount_in: args.amount_in,
         expect_amount_out: args.expect_amount_out,
         min_return: args.min_return,
         amounts: args.amounts,
         routes: args.routes,
-    };
+    }
+    .into();
     let amount_out = swap_process(
         &mut ctx.accounts.source_token_account,
         &mut ctx.accounts.destination_token_account,
@@ -1,6 +1,7 @@
 use crate::adapters::{
-    aldrin, fluxbeam, lifinity, meteora, obric_v2, openbookv2, phoenix, pumpfun, raydium, sanctum, spl_token_swap, stable_swap, whirlpool
+    aldrin, crema, fluxbeam, gamma, invariant, lifinity, manifest, marinade, meteora, obric_v2, openbookv2, perena, phoenix, pumpfun, pumpswap, raydium, raydium_launchlab, sanctum, sanctum_router, saros, solfi, spl_token_swap, stabble, stable_swap, stake_pool, whirlpool, zerofi
 };
+use crate::adapters::common::check_amount_in_remainder;
 use crate::error::ErrorCode;
 use crate::utils::token::{transfer_token_from_sa_pda, transfer_token_from_user};
 use crate::{MAX_HOPS, TOTAL_WEIGHT, ZERO_ADDRESS};
@@ -34,12 +35,39 @@ pub enum Dex {
     SanctumWsolSwap,
     PumpfunBuy,
     PumpfunSell,
+    PumpSwapBuy,
+    PumpSwapSell,
+    MeteoraDammV2,
+    RaydiumLaunchlabBuy,
+    RaydiumLaunchlabSell,
+    WhirlpoolTwoHop,
+    MeteoraDlmmSwap2,
+    RaydiumSwapV2,
+    Manifest,
+    Invariant,
+    Crema,
+    SarosDlmm,
+    StabbleStable,
+    StabbleWeighted,
+    Perena,
+    SolFi,
+    ZeroFi,
+    Gamma,
+    SplStakePoolDepositSol,
+    SplStakePoolWithdrawSol,
+    MarinadeDeposit,
+    MarinadeLiquidUnstake,
+    SanctumRouterStakeWrappedSol,
+    SanctumRouterWithdrawWrappedSol,
+    SanctumRouterSwapViaStake,
 }
 #[derive(Debug)]
 pub struct HopAccounts {
     pub last_to_account: Pubkey,
     pub from_account: Pubkey,
     pub to_account: Pubkey,
+    pub amount_in_remainder: u64, // unfilled amount_in left in the source account by the last dex
+    pub amount_in_dust_limit: u64, // remainder the last dex leaves even when fully filled, e.g. below one lot
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -48,6 +76,147 @@ pub struct Route {
     pub weights: Vec<u8>,
 }
 
+// Route of the v2 instructions, with explicit accounts counts and params per dex.
+// A hop with an optional oracle leg must carry accounts_lens to locate the legs' accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RouteV2 {
+    pub dexes: Vec<Dex>,
+    pub weights: Vec<u8>,
+    pub accounts_lens: Vec<u8>, // accounts count of each dex, empty to use the fixed lengths
+    pub dex_params: Vec<DexParams>, // params of each dex, empty to use the defaults
+}
+
+impl From<Route> for RouteV2 {
+    fn from(route: Route) -> Self {
+        Self {
+            dexes: route.dexes,
+            weights: route.weights,
+            accounts_lens: vec![],
+            dex_params: vec![],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ClmmParams {
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit_x64: u128,
+}
+
+impl Default for ClmmParams {
+    fn default() -> Self {
+        Self {
+            other_amount_threshold: 1,
+            sqrt_price_limit_x64: 0,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TokenIndexParams {
+    pub in_index: u8,
+    pub out_index: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct OpenBookParams {
+    pub limit_price_x64: u128, // quote per base in native units, Q64.64, 0 for no limit
+    pub match_limit: u8,       // max orders to match, 0 for the default
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct PhoenixParams {
+    pub expect_amount_out: u64, // expected amount out of the leg, 0 for no min fill
+    pub slippage_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct OracleParams {
+    pub max_staleness_slots: u64, // 0 for no check
+    pub optional: bool,           // skip the leg when stale instead of failing the swap
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct StakeBridgeParams {
+    pub bridge_stake_seed: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct TransferHookParams {
+    pub hook_x_len: u8, // accounts of the token X transfer hook, 0 for mints without a hook
+    pub hook_y_len: u8, // accounts of the token Y transfer hook, 0 for mints without a hook
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DexParams {
+    None,
+    Clmm(ClmmParams),
+    TokenIndex(TokenIndexParams),
+    StakeBridge(StakeBridgeParams),
+    OpenBook(OpenBookParams),
+    Phoenix(PhoenixParams),
+    Oracle(OracleParams),
+    TransferHook(TransferHookParams),
+}
+
+impl DexParams {
+    pub fn clmm(&self) -> Result<ClmmParams> {
+        match self {
+            DexParams::None => Ok(ClmmParams::default()),
+            DexParams::Clmm(params) => Ok(*params),
+            _ => Err(ErrorCode::InvalidDexParams.into()),
+        }
+    }
+
+    pub fn open_book(&self) -> Result<OpenBookParams> {
+        match self {
+            DexParams::None => Ok(OpenBookParams::default()),
+            DexParams::OpenBook(params) => Ok(*params),
+            _ => Err(ErrorCode::InvalidDexParams.into()),
+        }
+    }
+
+    pub fn phoenix(&self) -> Result<PhoenixParams> {
+        match self {
+            DexParams::None => Ok(PhoenixParams::default()),
+            DexParams::Phoenix(params) => Ok(*params),
+            _ => Err(ErrorCode::InvalidDexParams.into()),
+        }
+    }
+
+    pub fn oracle(&self) -> Result<OracleParams> {
+        match self {
+            DexParams::None => Ok(OracleParams::default()),
+            DexParams::Oracle(params) => Ok(*params),
+            _ => Err(ErrorCode::InvalidDexParams.into()),
+        }
+    }
+
+    pub fn transfer_hook(&self) -> Result<TransferHookParams> {
+        match self {
+            DexParams::None => Ok(TransferHookParams::default()),
+            DexParams::TransferHook(params) => Ok(*params),
+            _ => Err(ErrorCode::InvalidDexParams.into()),
+        }
+    }
+
+    pub fn stake_bridge(&self) -> Result<StakeBridgeParams> {
+        match self {
+            DexParams::None => Ok(StakeBridgeParams::default()),
+            DexParams::StakeBridge(params) => Ok(*params),
+            _ => Err(ErrorCode::InvalidDexParams.into()),
+        }
+    }
+
+    // multi-asset pools have no default, the route must name the token slots
+    pub fn token_index(&self) -> Result<TokenIndexParams> {
+        match self {
+            DexParams::TokenIndex(params) => Ok(*params),
+            _ => Err(ErrorCode::InvalidDexParams.into()),
+        }
+    }
+}
+
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct SwapArgs {
     pub amount_in: u64,
@@ -57,6 +226,31 @@ pub struct SwapArgs {
     pub routes: Vec<Vec<Route>>, // 2nd level split route
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SwapArgsV2 {
+    pub amount_in: u64,
+    pub expect_amount_out: u64,
+    pub min_return: u64,
+    pub amounts: Vec<u64>,         // 1st level split amount
+    pub routes: Vec<Vec<RouteV2>>, // 2nd level split route
+}
+
+impl From<SwapArgs> for SwapArgsV2 {
+    fn from(args: SwapArgs) -> Self {
+        Self {
+            amount_in: args.amount_in,
+            expect_amount_out: args.expect_amount_out,
+            min_return: args.min_return,
+            amounts: args.amounts,
+            routes: args
+                .routes
+                .into_iter()
+                .map(|hops| hops.into_iter().map(RouteV2::from).collect())
+                .collect(),
+        }
+    }
+}
+
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct CommissionSwapArgs {
     pub amount_in: u64,
@@ -69,6 +263,22 @@ pub struct CommissionSwapArgs {
     pub commission_direction: bool, // Commission direction: true-fromToken, false-toToken
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct CommissionFeeRouteSwapArgs {
+    pub amount_in: u64,
+    pub expect_amount_out: u64,
+    pub min_return: u64,
+    pub amounts: Vec<u64>,         // 1st level split amount
+    pub routes: Vec<Vec<RouteV2>>, // 2nd level split route
+
+    pub commission_rate: u16,       // Commission rate
+    pub commission_direction: bool, // Commission direction: true-fromToken, false-toToken
+
+    pub fee_route: Vec<RouteV2>, // Route converting the commission into the fee mint
+    pub fee_min_return: u64,   // Min return of the fee route
+    pub fee_offset: u8,        // Offset of the fee route accounts in remaining_accounts
+}
+
 #[event]
 #[derive(Debug)]
 pub struct SwapEvent {
@@ -89,7 +299,7 @@ pub fn proxy_swap_process<'info>(
     source_token_program: &Interface<'info, TokenInterface>,
     destination_token_program: &Interface<'info, TokenInterface>,
     remaining_accounts: &'info [AccountInfo<'info>],
-    args: SwapArgs,
+    args: SwapArgsV2,
     order_id: u64,
 ) -> Result<u64> {
     let before_source_balance = source_token_account.amount;
@@ -105,6 +315,8 @@ pub fn proxy_swap_process<'info>(
     );
 
     // 1.Transfer source token to source_token_sa
+    let amount_in = args.amount_in;
+    let mut before_source_sa_balance = 0;
     let mut source_account = if let Some(source_token_sa) = source_token_sa {
         transfer_token_from_user(
             payer.to_account_info(),
@@ -115,6 +327,8 @@ pub fn proxy_swap_process<'info>(
             args.amount_in,
             source_mint.decimals,
         )?;
+        source_token_sa.reload()?;
+        before_source_sa_balance = source_token_sa.amount;
         source_token_sa.clone()
     } else {
         source_token_account.clone()
@@ -137,6 +351,17 @@ pub fn proxy_swap_process<'info>(
         source_token_sa.is_some(),
     )?;
     msg!("Swap amount_out: {}", amount_out);
+    if source_token_sa.is_some() {
+        refund_amount_in_remainder(
+            sa_authority,
+            &mut source_account,
+            source_token_account,
+            source_mint,
+            source_token_program,
+            before_source_sa_balance,
+            amount_in,
+        )?;
+    }
 
     // 3. Transfer destination token to destination_token_account
     if let Some(ref destination_token_sa) = destination_token_sa {
@@ -177,13 +402,45 @@ pub fn proxy_swap_process<'info>(
     Ok(amount_out)
 }
 
+// Return the amount_in left unfilled in the SA source account to the user's source account
+pub fn refund_amount_in_remainder<'info>(
+    sa_authority: &AccountInfo<'info>,
+    source_token_sa: &mut InterfaceAccount<'info, TokenAccount>,
+    source_token_account: &InterfaceAccount<'info, TokenAccount>,
+    source_mint: &InterfaceAccount<'info, Mint>,
+    source_token_program: &Interface<'info, TokenInterface>,
+    before_source_sa_balance: u64,
+    amount_in: u64,
+) -> Result<()> {
+    source_token_sa.reload()?;
+    let amount_in_used = before_source_sa_balance
+        .checked_sub(source_token_sa.amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    let amount_in_remainder = amount_in
+        .checked_sub(amount_in_used)
+        .ok_or(ErrorCode::CalculationError)?;
+    if amount_in_remainder > 0 {
+        transfer_token_from_sa_pda(
+            sa_authority.to_account_info(),
+            source_token_sa.to_account_info(),
+            source_token_account.to_account_info(),
+            source_mint.to_account_info(),
+            source_token_program.to_account_info(),
+            amount_in_remainder,
+            source_mint.decimals,
+        )?;
+        msg!("amount_in_remainder refunded: {}", amount_in_remainder);
+    }
+    Ok(())
+}
+
 pub fn swap_process<'info>(
     source_token_account: &mut InterfaceAccount<'info, TokenAccount>,
     destination_token_account: &mut InterfaceAccount<'info, TokenAccount>,
     source_mint: &InterfaceAccount<'info, Mint>,
     destination_mint: &InterfaceAccount<'info, Mint>,
     remaining_accounts: &'info [AccountInfo<'info>],
-    args: SwapArgs,
+    args: SwapArgsV2,
     order_id: u64,
     proxy_swap: bool,
 ) -> Result<u64> {
@@ -191,7 +448,7 @@ pub fn swap_process<'info>(
         msg!("order_id: {}", order_id);
     }
     // Check SwapArgs
-    let SwapArgs {
+    let SwapArgsV2 {
         amount_in,
         min_return,
         expect_amount_out,
@@ -251,10 +508,20 @@ pub fn swap_process<'info>(
         for (hop, route) in hops.iter().enumerate() {
             let dexes = &route.dexes;
             let weights = &route.weights;
+            let accounts_lens = &route.accounts_lens;
+            let dex_params = &route.dex_params;
             require!(
                 dexes.len() == weights.len(),
                 ErrorCode::DexesAndWeightsMustHaveTheSameLength
             );
+            require!(
+                accounts_lens.is_empty() || dexes.len() == accounts_lens.len(),
+                ErrorCode::DexesAndAccountsLensMustHaveTheSameLength
+            );
+            require!(
+                dex_params.is_empty() || dexes.len() == dex_params.len(),
+                ErrorCode::DexesAndDexParamsMustHaveTheSameLength
+            );
             let total_weight: u8 = weights.iter().try_fold(0u8, |acc, &x| {
                 acc.checked_add(x).ok_or(ErrorCode::CalculationError)
             })?;
@@ -265,12 +532,43 @@ pub fn swap_process<'info>(
                 last_to_account,
                 from_account: ZERO_ADDRESS,
                 to_account: ZERO_ADDRESS,
+                amount_in_remainder: 0,
+                amount_in_dust_limit: 0,
             };
+
+            // Skipped legs give their weight to the other legs of the hop
+            let skipped = get_skipped_dexes(
+                dexes,
+                accounts_lens,
+                dex_params,
+                remaining_accounts,
+                offset,
+            )?;
+            let active_weight: u64 = weights
+                .iter()
+                .zip(skipped.iter())
+                .filter(|(_, skip)| !**skip)
+                .map(|(weight, _)| *weight as u64)
+                .sum();
+            require!(active_weight > 0, ErrorCode::OracleStale);
+            let last_active_index = skipped
+                .iter()
+                .rposition(|skip| !*skip)
+                .ok_or(ErrorCode::OracleStale)?;
+
             let mut amount_out: u64 = 0;
             let mut acc_fork_in: u64 = 0;
             for (index, dex) in dexes.iter().enumerate() {
+                if skipped[index] {
+                    msg!("skip dex: {:?}", dex);
+                    offset = offset
+                        .checked_add(accounts_lens[index] as usize)
+                        .ok_or(ErrorCode::CalculationError)?;
+                    continue;
+                }
+
                 // Calculate 2 level split amount
-                let fork_amount_in = if index == dexes.len() - 1 {
+                let split_amount_in = if index == last_active_index {
                     // The last dex, use the remaining amount_in for trading to prevent accumulation
                     amount_in
                         .checked_sub(acc_fork_in)
@@ -279,29 +577,68 @@ pub fn swap_process<'info>(
                     let temp_amount = amount_in
                         .checked_mul(weights[index] as u64)
                         .ok_or(ErrorCode::CalculationError)?
-                        .checked_div(TOTAL_WEIGHT as u64)
+                        .checked_div(active_weight)
                         .ok_or(ErrorCode::CalculationError)?;
                     acc_fork_in = acc_fork_in
                         .checked_add(temp_amount)
                         .ok_or(ErrorCode::CalculationError)?;
                     temp_amount
                 };
+                // The amount left unfilled by the previous dex goes to the next one
+                let fork_amount_in = split_amount_in
+                    .checked_add(hop_accounts.amount_in_remainder)
+                    .ok_or(ErrorCode::CalculationError)?;
+                hop_accounts.amount_in_remainder = 0;
+                hop_accounts.amount_in_dust_limit = 0;
+
+                // Pass only the dex's own accounts when the route carries its accounts count
+                let accounts_len = accounts_lens.get(index).map(|len| *len as usize);
+                let (dex_accounts, mut dex_offset) = match accounts_len {
+                    Some(len) => {
+                        let (min_len, max_len) = get_dex_account_len_bounds(dex)?;
+                        require!(
+                            len >= min_len && len <= max_len,
+                            ErrorCode::InvalidAccountsLength
+                        );
+                        let end = offset
+                            .checked_add(len)
+                            .ok_or(ErrorCode::CalculationError)?;
+                        require!(
+                            end <= remaining_accounts.len(),
+                            ErrorCode::InvalidAccountsLength
+                        );
+                        (&remaining_accounts[offset..end], 0)
+                    }
+                    None => (remaining_accounts, offset),
+                };
 
                 // Execute swap
                 let fork_amount_out = excute_swap(
                     dex,
-                    remaining_accounts,
+                    dex_accounts,
                     fork_amount_in,
-                    &mut offset,
+                    &mut dex_offset,
                     &mut hop_accounts,
                     hop,
                     proxy_swap,
+                    accounts_len,
+                    dex_params.get(index).copied().unwrap_or(DexParams::None),
                 )?;
+                match accounts_len {
+                    Some(len) => {
+                        // CHECK: the adapter must consume exactly the declared accounts
+                        require!(dex_offset == len, ErrorCode::InvalidAccountsLength);
+                        offset += len;
+                    }
+                    None => offset = dex_offset,
+                }
 
                 // Emit SwapEvent
                 let event = SwapEvent {
                     dex: *dex,
-                    amount_in: fork_amount_in,
+                    amount_in: fork_amount_in
+                        .checked_sub(hop_accounts.amount_in_remainder)
+                        .ok_or(ErrorCode::CalculationError)?,
                     amount_out: fork_amount_out,
                 };
                 emit!(event);
@@ -314,6 +651,14 @@ pub fn swap_process<'info>(
                     .ok_or(ErrorCode::CalculationError)?;
             }
 
+            if hop_accounts.amount_in_remainder > 0 {
+                // The last dex of the hop left it in the source account. At the first hop it is the
+                // swap's source account, which proxy callers refund to the user with
+                // refund_amount_in_remainder
+                check_amount_in_remainder(hop, &hop_accounts)?;
+                msg!("amount_in_remainder: {}", hop_accounts.amount_in_remainder);
+            }
+
             if hop == 0 {
                 // CHECK: Verify the first hop's from_token must be consistent with ctx.accounts.source_token_account
                 require!(
@@ -333,13 +678,10 @@ pub fn swap_process<'info>(
         }
     }
 
-    //source_token_account.reload()?;
-    
-    // source token account has been closed in pumpfun buy
+    // source token account has been closed in a legacy pumpfun buy
     if source_token_account.get_lamports() != 0 {
         source_token_account.reload()?;
     }
-
     destination_token_account.reload()?;
     let after_source_balance = source_token_account.amount;
     let after_destination_balance = destination_token_account.amount;
@@ -369,6 +711,7 @@ pub fn swap_process<'info>(
     Ok(destination_token_change)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn excute_swap<'a>(
     dex: &Dex,
     remaining_accounts: &'a [AccountInfo<'a>],
@@ -377,6 +720,8 @@ fn excute_swap<'a>(
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
+    accounts_len: Option<usize>,
+    dex_params: DexParams,
 ) -> Result<u64> {
     let swap_function = match dex {
         Dex::SplTokenSwap => spl_token_swap::swap,
@@ -385,25 +730,200 @@ fn excute_swap<'a>(
         Dex::MeteoraDynamicpool => meteora::swap,
         Dex::RaydiumSwap => raydium::swap,
         Dex::RaydiumStableSwap => raydium::swap_stable,
-        Dex::RaydiumClmmSwap => raydium::swap_clmm,
-        Dex::RaydiumClmmSwapV2 => raydium::swap_clmm_v2,
+        Dex::RaydiumClmmSwap => {
+            return raydium::swap_clmm(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+                dex_params.clmm()?,
+            )
+        }
+        Dex::RaydiumClmmSwapV2 => {
+            return raydium::swap_clmm_v2(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+                dex_params.clmm()?,
+            )
+        }
         Dex::AldrinExchangeV1 => aldrin::swap_v1,
         Dex::AldrinExchangeV2 => aldrin::swap_v2,
         Dex::LifinityV1 => lifinity::swap_v1,
-        Dex::LifinityV2 => lifinity::swap_v2,
+        Dex::LifinityV2 => {
+            return lifinity::swap_v2(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                dex_params.oracle()?,
+            )
+        }
         Dex::FluxBeam => fluxbeam::swap,
         Dex::MeteoraDlmm => meteora::swap_dlmm,
         Dex::RaydiumCpmmSwap => raydium::swap_cpmm,
-        Dex::OpenBookV2 => openbookv2::place_take_order,
-        Dex::WhirlpoolV2 => whirlpool::swap_v2,
-        Dex::Phoenix => phoenix::swap,
+        Dex::OpenBookV2 => {
+            return openbookv2::place_take_order(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                dex_params.open_book()?,
+            )
+        }
+        Dex::WhirlpoolV2 => {
+            return whirlpool::swap_v2(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+            )
+        }
+        Dex::Phoenix => {
+            return phoenix::swap(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                dex_params.phoenix()?,
+            )
+        }
         Dex::ObricV2 => obric_v2::swap,
         Dex::SanctumAddLiq => sanctum::add_liquidity_handler,
         Dex::SanctumRemoveLiq => sanctum::remove_liquidity_handler,
         Dex::SanctumNonWsolSwap => sanctum::swap_without_wsol_handler,
         Dex::SanctumWsolSwap => sanctum::swap_with_wsol_handler,
-        Dex::PumpfunBuy => pumpfun::buy,
+        Dex::PumpfunBuy => {
+            return pumpfun::buy(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+            )
+        }
         Dex::PumpfunSell => pumpfun::sell,
+        Dex::PumpSwapBuy => pumpswap::buy,
+        Dex::PumpSwapSell => pumpswap::sell,
+        Dex::MeteoraDammV2 => meteora::swap_damm_v2,
+        Dex::RaydiumLaunchlabBuy => raydium_launchlab::buy,
+        Dex::RaydiumLaunchlabSell => raydium_launchlab::sell,
+        Dex::WhirlpoolTwoHop => whirlpool::two_hop_swap_v2,
+        Dex::RaydiumSwapV2 => raydium::swap_v2,
+        Dex::Manifest => manifest::swap,
+        Dex::Invariant => {
+            return invariant::swap(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+            )
+        }
+        Dex::Crema => {
+            return crema::swap(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+            )
+        }
+        Dex::SarosDlmm => saros::swap,
+        Dex::StabbleStable => stabble::swap_stable,
+        Dex::StabbleWeighted => stabble::swap_weighted,
+        Dex::SolFi => solfi::swap,
+        Dex::ZeroFi => zerofi::swap,
+        Dex::Gamma => gamma::swap,
+        Dex::SplStakePoolDepositSol => stake_pool::deposit_sol,
+        Dex::SplStakePoolWithdrawSol => stake_pool::withdraw_sol,
+        Dex::MarinadeDeposit => marinade::deposit,
+        Dex::MarinadeLiquidUnstake => marinade::liquid_unstake,
+        Dex::SanctumRouterStakeWrappedSol => {
+            // stake pool accounts are variable, so the route must carry the count
+            let accounts_len = accounts_len.ok_or(ErrorCode::InvalidAccountsLength)?;
+            return sanctum_router::stake_wrapped_sol(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+            );
+        }
+        Dex::SanctumRouterWithdrawWrappedSol => {
+            let accounts_len = accounts_len.ok_or(ErrorCode::InvalidAccountsLength)?;
+            return sanctum_router::withdraw_wrapped_sol(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+            );
+        }
+        Dex::SanctumRouterSwapViaStake => {
+            let accounts_len = accounts_len.ok_or(ErrorCode::InvalidAccountsLength)?;
+            return sanctum_router::swap_via_stake(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+                dex_params.stake_bridge()?,
+            );
+        }
+        Dex::Perena => {
+            return perena::swap(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                dex_params.token_index()?,
+            )
+        }
+        Dex::MeteoraDlmmSwap2 => {
+            // bin arrays and transfer hook accounts are variable, so the route must carry the count
+            let accounts_len = accounts_len.ok_or(ErrorCode::InvalidAccountsLength)?;
+            return meteora::swap_dlmm_swap2(
+                remaining_accounts,
+                amount_in,
+                offset,
+                hop_accounts,
+                hop,
+                proxy_swap,
+                accounts_len,
+                dex_params.transfer_hook()?,
+            );
+        }
     };
     swap_function(
         remaining_accounts,
@@ -440,10 +960,126 @@ pub fn get_dex_account_len<'a>(
         Dex::ObricV2 => obric_v2::ACCOUNTS_LEN,
         Dex::SanctumAddLiq => 0,
         Dex::SanctumRemoveLiq => 0,
-        Dex::SanctumNonWsolSwap => 0,
-        Dex::SanctumWsolSwap => 0,
+        Dex::SanctumNonWsolSwap => sanctum::SWAP_LST_LST_ACCOUNTS_LEN,
+        Dex::SanctumWsolSwap => sanctum::SWAP_LST_SOL_ACCOUNTS_LEN,
         Dex::PumpfunBuy => pumpfun::BUY_ACCOUNTS_LEN,
         Dex::PumpfunSell => pumpfun::SELL_ACCOUNTS_LEN,
+        Dex::PumpSwapBuy => pumpswap::ACCOUNTS_LEN,
+        Dex::PumpSwapSell => pumpswap::ACCOUNTS_LEN,
+        Dex::MeteoraDammV2 => meteora::DAMM_V2_ACCOUNTS_LEN,
+        Dex::RaydiumLaunchlabBuy => raydium_launchlab::ACCOUNTS_LEN,
+        Dex::RaydiumLaunchlabSell => raydium_launchlab::ACCOUNTS_LEN,
+        Dex::WhirlpoolTwoHop => whirlpool::ACCOUNTS_TWO_HOP_LEN,
+        Dex::MeteoraDlmmSwap2 => meteora::DLMM_SWAP2_ACCOUNTS_LEN,
+        Dex::RaydiumSwapV2 => raydium::V2_ACCOUNTS_LEN,
+        Dex::Manifest => manifest::ACCOUNTS_LEN,
+        Dex::Invariant => invariant::ACCOUNTS_LEN,
+        Dex::Crema => crema::ACCOUNTS_LEN,
+        Dex::SarosDlmm => saros::ACCOUNTS_LEN,
+        Dex::StabbleStable => stabble::ACCOUNTS_LEN,
+        Dex::StabbleWeighted => stabble::ACCOUNTS_LEN,
+        Dex::Perena => perena::ACCOUNTS_LEN,
+        Dex::SolFi => solfi::ACCOUNTS_LEN,
+        Dex::ZeroFi => zerofi::ACCOUNTS_LEN,
+        Dex::Gamma => gamma::ACCOUNTS_LEN,
+        Dex::SplStakePoolDepositSol => stake_pool::DEPOSIT_SOL_ACCOUNTS_LEN,
+        Dex::SplStakePoolWithdrawSol => stake_pool::WITHDRAW_SOL_ACCOUNTS_LEN,
+        Dex::MarinadeDeposit => marinade::DEPOSIT_ACCOUNTS_LEN,
+        Dex::MarinadeLiquidUnstake => marinade::LIQUID_UNSTAKE_ACCOUNTS_LEN,
+        Dex::SanctumRouterStakeWrappedSol => sanctum_router::STAKE_WRAPPED_SOL_ACCOUNTS_LEN,
+        Dex::SanctumRouterWithdrawWrappedSol => sanctum_router::WITHDRAW_WRAPPED_SOL_ACCOUNTS_LEN,
+        Dex::SanctumRouterSwapViaStake => sanctum_router::SWAP_VIA_STAKE_ACCOUNTS_LEN,
     };
     Ok(account_len)
-}
\ No newline at end of file
+}
+
+// Min and max accounts count of the dex, for routes carrying explicit accounts counts
+pub fn get_dex_account_len_bounds(dex: &Dex) -> Result<(usize, usize)> {
+    let bounds = match dex {
+        Dex::WhirlpoolV2 => (whirlpool::ACCOUNTS_V2_LEN, whirlpool::MAX_ACCOUNTS_V2_LEN),
+        Dex::PumpfunBuy => (pumpfun::BUY_ACCOUNTS_LEN, pumpfun::BUY_TEMP_WSOL_ACCOUNTS_LEN),
+        Dex::RaydiumClmmSwap => (
+            raydium::CLMM_FIXED_ACCOUNTS_LEN + 1,
+            raydium::MAX_CLMM_ACCOUNTS_LEN,
+        ),
+        Dex::RaydiumClmmSwapV2 => (
+            raydium::CLMM_V2_FIXED_ACCOUNTS_LEN + 1,
+            raydium::MAX_CLMM_V2_ACCOUNTS_LEN,
+        ),
+        Dex::Invariant => (invariant::ACCOUNTS_LEN, invariant::MAX_ACCOUNTS_LEN),
+        Dex::Crema => (crema::FIXED_ACCOUNTS_LEN + 1, crema::MAX_ACCOUNTS_LEN),
+        Dex::MeteoraDlmmSwap2 => (
+            meteora::DLMM_SWAP2_ACCOUNTS_LEN + 1,
+            meteora::MAX_DLMM_SWAP2_ACCOUNTS_LEN,
+        ),
+        Dex::SanctumRouterStakeWrappedSol => (
+            sanctum_router::STAKE_WRAPPED_SOL_ACCOUNTS_LEN + 1,
+            sanctum_router::STAKE_WRAPPED_SOL_ACCOUNTS_LEN + sanctum_router::MAX_POOL_ACCOUNTS_LEN,
+        ),
+        Dex::SanctumRouterWithdrawWrappedSol => (
+            sanctum_router::WITHDRAW_WRAPPED_SOL_ACCOUNTS_LEN + 1,
+            sanctum_router::WITHDRAW_WRAPPED_SOL_ACCOUNTS_LEN
+                + sanctum_router::MAX_POOL_ACCOUNTS_LEN,
+        ),
+        Dex::SanctumRouterSwapViaStake => (
+            sanctum_router::SWAP_VIA_STAKE_ACCOUNTS_LEN + 1,
+            sanctum_router::SWAP_VIA_STAKE_ACCOUNTS_LEN + 2 * sanctum_router::MAX_POOL_ACCOUNTS_LEN,
+        ),
+        Dex::SanctumAddLiq => (
+            sanctum::ADD_WSOL_LIQ_ACCOUNTS_LEN,
+            sanctum::ADD_LST_LIQ_ACCOUNTS_LEN,
+        ),
+        Dex::SanctumRemoveLiq => (
+            sanctum::REMOVE_WSOL_LIQ_ACCOUNTS_LEN,
+            sanctum::REMOVE_LST_LIQ_ACCOUNTS_LEN,
+        ),
+        _ => {
+            let account_len = get_dex_account_len(dex)?;
+            (account_len, account_len)
+        }
+    };
+    Ok(bounds)
+}
+
+// Optional legs whose oracle is stale, found before the hop is split
+fn get_skipped_dexes(
+    dexes: &[Dex],
+    accounts_lens: &[u8],
+    dex_params: &[DexParams],
+    remaining_accounts: &[AccountInfo],
+    offset: usize,
+) -> Result<Vec<bool>> {
+    let mut skipped = vec![false; dexes.len()];
+    let has_optional = dex_params.iter().any(|params| {
+        matches!(
+            params,
+            DexParams::Oracle(OracleParams { optional: true, .. })
+        )
+    });
+    if !has_optional {
+        return Ok(skipped);
+    }
+
+    // The legs' accounts can only be located with explicit accounts counts
+    require!(
+        !accounts_lens.is_empty(),
+        ErrorCode::OptionalDexRequiresAccountsLens
+    );
+    require!(
+        accounts_lens.len() == dexes.len(),
+        ErrorCode::DexesAndAccountsLensMustHaveTheSameLength
+    );
+    let mut dex_offset = offset;
+    for (index, dex) in dexes.iter().enumerate() {
+        if let DexParams::Oracle(params) = dex_params[index] {
+            if params.optional && *dex == Dex::LifinityV2 {
+                skipped[index] =
+                    lifinity::is_oracle_stale(remaining_accounts, dex_offset, &params)?;
+            }
+        }
+        dex_offset = dex_offset
+            .checked_add(accounts_lens[index] as usize)
+            .ok_or(ErrorCode::CalculationError)?;
+    }
+    Ok(skipped)
+}
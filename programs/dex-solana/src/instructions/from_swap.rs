@@ -1,5 +1,6 @@
+use crate::utils::check_to_address;
 use crate::BRIDGE_TO_LOG_SELECTOR;
-use crate::{error::ErrorCode, swap_process, SwapArgs};
+use crate::{error::ErrorCode, swap_process, SwapArgsV2};
 use anchor_lang::{
     prelude::*,
     solana_program::{instruction::Instruction, program::invoke},
@@ -110,7 +111,7 @@ pub enum AdaptorID {
 
 pub fn from_swap_log_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, FromSwapAccounts<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     bridge_to_args: BridgeToArgs,
     offset: u8,
     len: u8,
@@ -134,41 +135,150 @@ pub fn from_swap_log_handler<'a>(
         amount_out,
         offset,
         len,
-        &ctx.accounts.bridge_program,
-        &ctx.accounts.payer,
-        &ctx.accounts.destination_token_account,
+        BridgeToLogAccounts {
+            bridge_program: &ctx.accounts.bridge_program,
+            payer: &ctx.accounts.payer,
+            destination_token_account: &ctx.accounts.destination_token_account,
+            destination_mint: &ctx.accounts.destination_mint,
+            associated_token_program: &ctx.accounts.associated_token_program,
+            token_program: &ctx.accounts.token_program,
+            token_2022_program: &ctx.accounts.token_2022_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    Ok(())
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub enum BridgeSkipReason {
+    BelowBridgeMinAmount,
+    InvalidBridgeToArgs,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct BridgeSkipped {
+    pub order_id: u64,
+    pub amount: u64,
+    pub reason: BridgeSkipReason,
+}
+
+pub fn from_swap_log_with_fallback_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, FromSwapAccounts<'a>>,
+    args: SwapArgsV2,
+    bridge_to_args: BridgeToArgs,
+    offset: u8,
+    len: u8,
+    bridge_min_amount: u64,
+) -> Result<()> {
+    // CHECK: the swap output is left to the payer when the bridge is skipped
+    require!(
+        ctx.accounts.destination_token_account.owner == ctx.accounts.payer.key(),
+        ErrorCode::InvalidDestinationTokenAccount
+    );
+
+    // 1.Smart swap
+    let amount_out = swap_process(
+        &mut ctx.accounts.source_token_account,
+        &mut ctx.accounts.destination_token_account,
+        &ctx.accounts.source_mint,
         &ctx.accounts.destination_mint,
-        &ctx.accounts.associated_token_program,
-        &ctx.accounts.token_program,
-        &ctx.accounts.token_2022_program,
-        &ctx.accounts.system_program,
-        &ctx.remaining_accounts,
+        ctx.remaining_accounts,
+        args,
+        0,
+        false,
     )?;
+    msg!("Swap amount_out: {}", amount_out);
+
+    // 2. Skip bridge_to_log if the bridge would reject it
+    let skip_reason = if amount_out < bridge_min_amount {
+        Some(BridgeSkipReason::BelowBridgeMinAmount)
+    } else if check_bridge_to_args(&bridge_to_args, offset, len, ctx.remaining_accounts).is_err() {
+        Some(BridgeSkipReason::InvalidBridgeToArgs)
+    } else {
+        None
+    };
+    if let Some(reason) = skip_reason {
+        let event = BridgeSkipped {
+            order_id: bridge_to_args.order_id,
+            amount: amount_out,
+            reason,
+        };
+        emit!(event);
+        msg!("{:?}", event);
+        return Ok(());
+    }
+
+    // 3. CPI bridge_to_log
+    cpi_bridge_to_log(
+        bridge_to_args,
+        amount_out,
+        offset,
+        len,
+        BridgeToLogAccounts {
+            bridge_program: &ctx.accounts.bridge_program,
+            payer: &ctx.accounts.payer,
+            destination_token_account: &ctx.accounts.destination_token_account,
+            destination_mint: &ctx.accounts.destination_mint,
+            associated_token_program: &ctx.accounts.associated_token_program,
+            token_program: &ctx.accounts.token_program,
+            token_2022_program: &ctx.accounts.token_2022_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    Ok(())
+}
 
+pub fn check_bridge_to_args(
+    bridge_to_args: &BridgeToArgs,
+    offset: u8,
+    len: u8,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    require!(
+        remaining_accounts.len() >= offset as usize + len as usize,
+        ErrorCode::InvalidAccountsLength
+    );
+    check_to_address(bridge_to_args.to_chain_id, &bridge_to_args.to)?;
     Ok(())
 }
 
+pub struct BridgeToLogAccounts<'a, 'info> {
+    pub bridge_program: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub destination_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub destination_mint: &'a InterfaceAccount<'info, Mint>,
+    pub associated_token_program: &'a Program<'info, AssociatedToken>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub token_2022_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
 pub fn cpi_bridge_to_log<'info>(
     bridge_to_args: BridgeToArgs,
     amount_out: u64,
     offset: u8,
     len: u8,
-    bridge_program: &AccountInfo<'info>,
-    payer: &Signer<'info>,
-    destination_token_account: &InterfaceAccount<'info, TokenAccount>,
-    destination_mint: &InterfaceAccount<'info, Mint>,
-    associated_token_program: &Program<'info, AssociatedToken>,
-    token_program: &AccountInfo<'info>,
-    token_2022_program: &AccountInfo<'info>,
-    system_program: &AccountInfo<'info>,
+    bridge_accounts: BridgeToLogAccounts<'_, 'info>,
     remaining_accounts: &[AccountInfo<'info>],
 ) -> Result<()> {
+    let BridgeToLogAccounts {
+        bridge_program,
+        payer,
+        destination_token_account,
+        destination_mint,
+        associated_token_program,
+        token_program,
+        token_2022_program,
+        system_program,
+    } = bridge_accounts;
+    check_bridge_to_args(&bridge_to_args, offset, len, remaining_accounts)?;
     let offset = offset as usize;
     let len = len as usize;
-    require!(
-        remaining_accounts.len() >= offset + len,
-        ErrorCode::InvalidAccountsLength
-    );
     // get bridgeTo remaining accounts
     let bridge_remaining_accounts = Vec::from(&remaining_accounts[offset..offset + len]);
 
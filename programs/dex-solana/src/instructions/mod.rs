@@ -1,15 +1,23 @@
+pub mod commission_fee_route_swap;
 pub mod commission_from_swap;
+pub mod commission_proxy_from_swap;
 pub mod commission_proxy_swap;
 pub mod commission_swap;
 pub mod common;
 pub mod from_swap;
+pub mod proxy_from_swap;
 pub mod proxy_swap;
 pub mod swap;
+pub mod to_swap;
 
+pub use commission_fee_route_swap::*;
 pub use commission_from_swap::*;
+pub use commission_proxy_from_swap::*;
 pub use commission_proxy_swap::*;
 pub use commission_swap::*;
 pub use common::*;
 pub use from_swap::*;
+pub use proxy_from_swap::*;
 pub use proxy_swap::*;
 pub use swap::*;
+pub use to_swap::*;
@@ -0,0 +1,201 @@
+use crate::constants::*;
+use crate::instructions::from_swap::{cpi_bridge_to_log, BridgeToLogAccounts};
+use crate::utils::token::{transfer_token_from_sa_pda, transfer_token_from_user};
+use crate::{refund_amount_in_remainder, swap_process, BridgeToArgs, SwapArgsV2};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct ProxyFromSwapAccounts<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = payer,
+        token::token_program = source_token_program,
+    )]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub source_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub destination_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: sa_authority
+    #[account(
+        seeds = [
+            SEED_SA,
+        ],
+        bump = BUMP_SA,
+    )]
+    pub sa_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = source_mint,
+        associated_token::authority = sa_authority,
+        associated_token::token_program = source_token_program,
+    )]
+    pub source_token_sa: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = destination_mint,
+        associated_token::authority = sa_authority,
+        associated_token::token_program = destination_token_program,
+    )]
+    pub destination_token_sa: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The swap output is moved here before the bridge takes it, the SA authority never signs for the bridge
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = destination_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = destination_token_program,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: bridge_program
+    #[account(address = crate::okx_bridge_program::id())]
+    pub bridge_program: AccountInfo<'info>,
+
+    pub source_token_program: Interface<'info, TokenInterface>,
+    pub destination_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn proxy_from_swap_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, ProxyFromSwapAccounts<'a>>,
+    args: SwapArgsV2,
+    bridge_to_args: BridgeToArgs,
+    offset: u8,
+    len: u8,
+) -> Result<()> {
+    proxy_from_swap_process(
+        &ctx.accounts.payer,
+        &ctx.accounts.sa_authority,
+        &mut ctx.accounts.source_token_account,
+        &mut ctx.accounts.source_token_sa,
+        &mut ctx.accounts.destination_token_sa,
+        &ctx.accounts.destination_token_account,
+        &ctx.accounts.source_mint,
+        &ctx.accounts.destination_mint,
+        &ctx.accounts.source_token_program,
+        &ctx.accounts.destination_token_program,
+        &ctx.accounts.bridge_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_2022_program,
+        &ctx.accounts.system_program,
+        ctx.remaining_accounts,
+        args,
+        bridge_to_args,
+        offset,
+        len,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn proxy_from_swap_process<'info>(
+    payer: &Signer<'info>,
+    sa_authority: &UncheckedAccount<'info>,
+    source_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    source_token_sa: &mut InterfaceAccount<'info, TokenAccount>,
+    destination_token_sa: &mut InterfaceAccount<'info, TokenAccount>,
+    destination_token_account: &InterfaceAccount<'info, TokenAccount>,
+    source_mint: &InterfaceAccount<'info, Mint>,
+    destination_mint: &InterfaceAccount<'info, Mint>,
+    source_token_program: &Interface<'info, TokenInterface>,
+    destination_token_program: &Interface<'info, TokenInterface>,
+    bridge_program: &AccountInfo<'info>,
+    associated_token_program: &Program<'info, AssociatedToken>,
+    token_program: &Program<'info, Token>,
+    token_2022_program: &Program<'info, Token2022>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    args: SwapArgsV2,
+    bridge_to_args: BridgeToArgs,
+    offset: u8,
+    len: u8,
+) -> Result<()> {
+    // 1.Transfer source token to source_token_sa
+    transfer_token_from_user(
+        payer.to_account_info(),
+        source_token_account.to_account_info(),
+        source_token_sa.to_account_info(),
+        source_mint.to_account_info(),
+        source_token_program.to_account_info(),
+        args.amount_in,
+        source_mint.decimals,
+    )?;
+    source_token_sa.reload()?;
+    let before_source_sa_balance = source_token_sa.amount;
+    let amount_in = args.amount_in;
+
+    // 2.Smart swap
+    let amount_out = swap_process(
+        source_token_sa,
+        destination_token_sa,
+        source_mint,
+        destination_mint,
+        remaining_accounts,
+        args,
+        bridge_to_args.order_id,
+        true,
+    )?;
+    msg!("Swap amount_out: {}", amount_out);
+    refund_amount_in_remainder(
+        sa_authority,
+        source_token_sa,
+        source_token_account,
+        source_mint,
+        source_token_program,
+        before_source_sa_balance,
+        amount_in,
+    )?;
+
+    // 3. Move amount_out from destination_token_sa to the payer's destination_token_account
+    transfer_token_from_sa_pda(
+        sa_authority.to_account_info(),
+        destination_token_sa.to_account_info(),
+        destination_token_account.to_account_info(),
+        destination_mint.to_account_info(),
+        destination_token_program.to_account_info(),
+        amount_out,
+        destination_mint.decimals,
+    )?;
+
+    // 4. CPI bridge_to_log from destination_token_account
+    cpi_bridge_to_log(
+        bridge_to_args,
+        amount_out,
+        offset,
+        len,
+        BridgeToLogAccounts {
+            bridge_program,
+            payer,
+            destination_token_account,
+            destination_mint,
+            associated_token_program,
+            token_program,
+            token_2022_program,
+            system_program,
+        },
+        remaining_accounts,
+    )?;
+
+    Ok(())
+}
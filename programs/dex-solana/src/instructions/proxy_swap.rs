@@ -1,4 +1,4 @@
-use crate::{constants::*, proxy_swap_process, SwapArgs};
+use crate::{constants::*, proxy_swap_process, SwapArgsV2};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -62,7 +62,7 @@ pub struct ProxySwapAccounts<'info> {
 
 pub fn proxy_swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, ProxySwapAccounts<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     order_id: u64,
 ) -> Result<u64> {
     proxy_swap_process(
@@ -1,5 +1,5 @@
 use crate::swap_process;
-use crate::SwapArgs;
+use crate::SwapArgsV2;
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount};
 
@@ -27,7 +27,7 @@ pub struct SwapAccounts<'info> {
 
 pub fn swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, SwapAccounts<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     order_id: u64,
 ) -> Result<u64> {
     swap_process(
@@ -0,0 +1,222 @@
+use crate::utils::token::transfer_token_from_sa_pda;
+use crate::{constants::*, error::ErrorCode, swap_process, SwapArgsV2};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Settlement of a bridged order, created once by either to_swap or to_swap_refund
+#[account]
+#[derive(InitSpace)]
+pub struct ToSwapOrder {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub refunded: bool,
+}
+
+#[derive(Accounts)]
+#[instruction(data: SwapArgsV2, order_id: u64)]
+pub struct ToSwapAccounts<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: PDA of the bridge program, checked in check_bridge_caller
+    pub bridge_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ToSwapOrder::INIT_SPACE,
+        seeds = [
+            SEED_TO_SWAP_ORDER,
+            &order_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub order: Box<Account<'info, ToSwapOrder>>,
+
+    /// CHECK: sa_authority
+    #[account(
+        seeds = [
+            SEED_SA,
+        ],
+        bump = BUMP_SA,
+    )]
+    pub sa_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = source_mint,
+        associated_token::authority = sa_authority,
+        associated_token::token_program = source_token_program,
+    )]
+    pub source_token_sa: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: recipient of the bridged order
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = destination_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = destination_token_program,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub source_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub destination_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub source_token_program: Interface<'info, TokenInterface>,
+    pub destination_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, order_id: u64)]
+pub struct ToSwapRefundAccounts<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: PDA of the bridge program, checked in check_bridge_caller
+    pub bridge_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ToSwapOrder::INIT_SPACE,
+        seeds = [
+            SEED_TO_SWAP_ORDER,
+            &order_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub order: Box<Account<'info, ToSwapOrder>>,
+
+    /// CHECK: sa_authority
+    #[account(
+        seeds = [
+            SEED_SA,
+        ],
+        bump = BUMP_SA,
+    )]
+    pub sa_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = source_mint,
+        associated_token::authority = sa_authority,
+        associated_token::token_program = source_token_program,
+    )]
+    pub source_token_sa: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: recipient of the bridged order
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = source_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = source_token_program,
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub source_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub source_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn to_swap_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, ToSwapAccounts<'a>>,
+    args: SwapArgsV2,
+    order_id: u64,
+) -> Result<u64> {
+    check_bridge_caller(&ctx.accounts.bridge_authority)?;
+    msg!("recipient: {}", ctx.accounts.recipient.key());
+
+    // The order settles once, refunding it afterwards fails
+    let order = &mut ctx.accounts.order;
+    order.order_id = order_id;
+    order.recipient = ctx.accounts.recipient.key();
+    order.amount = args.amount_in;
+    order.refunded = false;
+    let amount_in = args.amount_in;
+    let before_source_sa_balance = ctx.accounts.source_token_sa.amount;
+
+    // Swap the bridged token held by source_token_sa to the recipient
+    let amount_out = swap_process(
+        &mut ctx.accounts.source_token_sa,
+        &mut ctx.accounts.destination_token_account,
+        &ctx.accounts.source_mint,
+        &ctx.accounts.destination_mint,
+        ctx.remaining_accounts,
+        args,
+        order_id,
+        true,
+    )?;
+    msg!("Swap amount_out: {}", amount_out);
+
+    // CHECK: the whole order is swapped, an unfilled remainder can only be refunded as a whole
+    let source_token_sa = &mut ctx.accounts.source_token_sa;
+    source_token_sa.reload()?;
+    let amount_in_used = before_source_sa_balance
+        .checked_sub(source_token_sa.amount)
+        .ok_or(ErrorCode::CalculationError)?;
+    require!(
+        amount_in_used == amount_in,
+        ErrorCode::AmountInRemainderNotRefunded
+    );
+    Ok(amount_out)
+}
+
+// Called by the bridge program when to_swap fails, e.g. min_return is not reached
+pub fn to_swap_refund_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, ToSwapRefundAccounts<'a>>,
+    amount: u64,
+    order_id: u64,
+) -> Result<()> {
+    check_bridge_caller(&ctx.accounts.bridge_authority)?;
+    require!(amount > 0, ErrorCode::AmountInMustBeGreaterThanZero);
+    msg!("order_id: {}", order_id);
+
+    // The order settles once, swapping it afterwards fails
+    let order = &mut ctx.accounts.order;
+    order.order_id = order_id;
+    order.recipient = ctx.accounts.recipient.key();
+    order.amount = amount;
+    order.refunded = true;
+
+    // Refund the bridged token to the recipient as is
+    transfer_token_from_sa_pda(
+        ctx.accounts.sa_authority.to_account_info(),
+        ctx.accounts.source_token_sa.to_account_info(),
+        ctx.accounts.recipient_token_account.to_account_info(),
+        ctx.accounts.source_mint.to_account_info(),
+        ctx.accounts.source_token_program.to_account_info(),
+        amount,
+        ctx.accounts.source_mint.decimals,
+    )?;
+    msg!(
+        "Refund recipient: {}, amount: {}",
+        ctx.accounts.recipient.key(),
+        amount
+    );
+    Ok(())
+}
+
+// Only the bridge program can sign for its PDA, so it built the order's amount and recipient
+fn check_bridge_caller(bridge_authority: &Signer) -> Result<()> {
+    let (bridge_authority_key, _) =
+        Pubkey::find_program_address(&[SEED_TO_SWAP_AUTHORITY], &okx_bridge_program::id());
+    require_keys_eq!(
+        bridge_authority.key(),
+        bridge_authority_key,
+        ErrorCode::InvalidBridgeCaller
+    );
+    Ok(())
+}
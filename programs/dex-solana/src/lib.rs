@@ -15,13 +15,22 @@ pub mod dex_solana {
     use super::*;
 
     pub fn swap<'a>(ctx: Context<'_, '_, 'a, 'a, SwapAccounts<'a>>, data: SwapArgs) -> Result<u64> {
-        instructions::swap_handler(ctx, data, 0)
+        instructions::swap_handler(ctx, data.into(), 0)
     }
 
     pub fn swap2<'a>(
         ctx: Context<'_, '_, 'a, 'a, SwapAccounts<'a>>,
         data: SwapArgs,
         order_id: u64,
+    ) -> Result<u64> {
+        instructions::swap_handler(ctx, data.into(), order_id)
+    }
+
+    // swap with explicit accounts counts and params per dex
+    pub fn swap_v2<'a>(
+        ctx: Context<'_, '_, 'a, 'a, SwapAccounts<'a>>,
+        data: SwapArgsV2,
+        order_id: u64,
     ) -> Result<u64> {
         instructions::swap_handler(ctx, data, order_id)
     }
@@ -56,6 +65,14 @@ pub mod dex_solana {
         instructions::commission_sol_swap_handler(ctx, data, order_id)
     }
 
+    pub fn commission_fee_route_swap<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionFeeRouteAccounts<'a>>,
+        data: CommissionFeeRouteSwapArgs,
+        order_id: u64,
+    ) -> Result<u64> {
+        instructions::commission_fee_route_swap_handler(ctx, data, order_id)
+    }
+
     pub fn from_swap_log<'a>(
         ctx: Context<'_, '_, 'a, 'a, FromSwapAccounts<'a>>,
         args: SwapArgs,
@@ -63,7 +80,41 @@ pub mod dex_solana {
         offset: u8,
         len: u8,
     ) -> Result<()> {
-        instructions::from_swap_log_handler(ctx, args, bridge_to_args, offset, len)
+        instructions::from_swap_log_handler(ctx, args.into(), bridge_to_args, offset, len)
+    }
+
+    pub fn from_swap_log_with_fallback<'a>(
+        ctx: Context<'_, '_, 'a, 'a, FromSwapAccounts<'a>>,
+        args: SwapArgsV2,
+        bridge_to_args: BridgeToArgs,
+        offset: u8,
+        len: u8,
+        bridge_min_amount: u64,
+    ) -> Result<()> {
+        instructions::from_swap_log_with_fallback_handler(
+            ctx,
+            args,
+            bridge_to_args,
+            offset,
+            len,
+            bridge_min_amount,
+        )
+    }
+
+    pub fn to_swap<'a>(
+        ctx: Context<'_, '_, 'a, 'a, ToSwapAccounts<'a>>,
+        data: SwapArgsV2,
+        order_id: u64,
+    ) -> Result<u64> {
+        instructions::to_swap_handler(ctx, data, order_id)
+    }
+
+    pub fn to_swap_refund<'a>(
+        ctx: Context<'_, '_, 'a, 'a, ToSwapRefundAccounts<'a>>,
+        amount: u64,
+        order_id: u64,
+    ) -> Result<()> {
+        instructions::to_swap_refund_handler(ctx, amount, order_id)
     }
 
     // proxy swap
@@ -71,6 +122,14 @@ pub mod dex_solana {
         ctx: Context<'_, '_, 'a, 'a, ProxySwapAccounts<'a>>,
         data: SwapArgs,
         order_id: u64,
+    ) -> Result<u64> {
+        instructions::proxy_swap_handler(ctx, data.into(), order_id)
+    }
+
+    pub fn proxy_swap_v2<'a>(
+        ctx: Context<'_, '_, 'a, 'a, ProxySwapAccounts<'a>>,
+        data: SwapArgsV2,
+        order_id: u64,
     ) -> Result<u64> {
         instructions::proxy_swap_handler(ctx, data, order_id)
     }
@@ -84,7 +143,7 @@ pub mod dex_solana {
     ) -> Result<u64> {
         instructions::commission_sol_proxy_swap_handler(
             ctx,
-            data,
+            data.into(),
             commission_rate,
             commission_direction,
             order_id,
@@ -100,7 +159,7 @@ pub mod dex_solana {
     ) -> Result<u64> {
         instructions::commission_spl_proxy_swap_handler(
             ctx,
-            data,
+            data.into(),
             commission_rate,
             commission_direction,
             order_id,
@@ -117,7 +176,7 @@ pub mod dex_solana {
     ) -> Result<()> {
         instructions::commission_sol_from_swap_handler(
             ctx,
-            args,
+            args.into(),
             commission_rate,
             bridge_to_args,
             offset,
@@ -134,6 +193,34 @@ pub mod dex_solana {
         len: u8,
     ) -> Result<()> {
         instructions::commission_spl_from_swap_handler(
+            ctx,
+            args.into(),
+            commission_rate,
+            bridge_to_args,
+            offset,
+            len,
+        )
+    }
+
+    pub fn proxy_from_swap<'a>(
+        ctx: Context<'_, '_, 'a, 'a, ProxyFromSwapAccounts<'a>>,
+        args: SwapArgsV2,
+        bridge_to_args: BridgeToArgs,
+        offset: u8,
+        len: u8,
+    ) -> Result<()> {
+        instructions::proxy_from_swap_handler(ctx, args, bridge_to_args, offset, len)
+    }
+
+    pub fn commission_proxy_from_swap<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionProxyFromSwapAccounts<'a>>,
+        args: SwapArgsV2,
+        commission_rate: u16,
+        bridge_to_args: BridgeToArgs,
+        offset: u8,
+        len: u8,
+    ) -> Result<()> {
+        instructions::commission_proxy_from_swap_handler(
             ctx,
             args,
             commission_rate,
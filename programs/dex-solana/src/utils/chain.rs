@@ -0,0 +1,286 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChainFamily {
+    Evm,
+    Solana,
+    Aptos,
+    Sui,
+    Tron,
+    Bitcoin,
+}
+
+// Chain registry: okx chain id => chain family
+pub const CHAIN_REGISTRY: &[(u64, ChainFamily)] = &[
+    (0, ChainFamily::Bitcoin),
+    (1, ChainFamily::Evm),   // Ethereum
+    (10, ChainFamily::Evm),  // Optimism
+    (25, ChainFamily::Evm),  // Cronos
+    (56, ChainFamily::Evm),  // BNB Chain
+    (137, ChainFamily::Evm), // Polygon
+    (169, ChainFamily::Evm), // Manta Pacific
+    (195, ChainFamily::Tron),
+    (196, ChainFamily::Evm), // X Layer
+    (204, ChainFamily::Evm), // opBNB
+    (250, ChainFamily::Evm), // Fantom
+    (324, ChainFamily::Evm), // zkSync Era
+    (501, ChainFamily::Solana),
+    (637, ChainFamily::Aptos),
+    (784, ChainFamily::Sui),
+    (1030, ChainFamily::Evm),   // Conflux eSpace
+    (1088, ChainFamily::Evm),   // Metis
+    (1101, ChainFamily::Evm),   // Polygon zkEVM
+    (5000, ChainFamily::Evm),   // Mantle
+    (8453, ChainFamily::Evm),   // Base
+    (42161, ChainFamily::Evm),  // Arbitrum One
+    (43114, ChainFamily::Evm),  // Avalanche C-Chain
+    (59144, ChainFamily::Evm),  // Linea
+    (81457, ChainFamily::Evm),  // Blast
+    (534352, ChainFamily::Evm), // Scroll
+];
+
+const EVM_ADDRESS_LEN: usize = 20;
+const PUBKEY_ADDRESS_LEN: usize = 32;
+const TRON_ADDRESS_PREFIX: u8 = 0x41;
+const BTC_P2PKH_VERSION: u8 = 0x00;
+const BTC_P2SH_VERSION: u8 = 0x05;
+const BTC_BECH32_HRP: &[u8] = b"bc";
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+pub fn get_chain_family(chain_id: u64) -> Option<ChainFamily> {
+    CHAIN_REGISTRY
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .map(|(_, family)| *family)
+}
+
+// Validate the bridge recipient `to` against the family of `to_chain_id`, chains missing from
+// the registry are left to the bridge
+pub fn check_to_address(to_chain_id: u64, to: &[u8]) -> Result<()> {
+    let Some(family) = get_chain_family(to_chain_id) else {
+        return Ok(());
+    };
+    let valid = match family {
+        ChainFamily::Evm => is_valid_raw_address(to, EVM_ADDRESS_LEN),
+        ChainFamily::Solana | ChainFamily::Aptos | ChainFamily::Sui => {
+            is_valid_raw_address(to, PUBKEY_ADDRESS_LEN)
+        }
+        ChainFamily::Tron => is_valid_tron_address(to),
+        ChainFamily::Bitcoin => is_valid_btc_address(to),
+    };
+    require!(valid, ErrorCode::InvalidBridgeToAddress);
+    Ok(())
+}
+
+fn is_valid_raw_address(to: &[u8], len: usize) -> bool {
+    to.len() == len && to.iter().any(|b| *b != 0)
+}
+
+// Tron: base58check of 0x41 + 20 bytes
+fn is_valid_tron_address(to: &[u8]) -> bool {
+    match base58check_decode(to) {
+        Some(payload) => payload.len() == 21 && payload[0] == TRON_ADDRESS_PREFIX,
+        None => false,
+    }
+}
+
+// Bitcoin: segwit bech32/bech32m, or base58check P2PKH/P2SH
+fn is_valid_btc_address(to: &[u8]) -> bool {
+    if to.len() > 3 && to[..3].eq_ignore_ascii_case(b"bc1") {
+        return is_valid_segwit_address(to);
+    }
+    match base58check_decode(to) {
+        Some(payload) => {
+            payload.len() == 21
+                && (payload[0] == BTC_P2PKH_VERSION || payload[0] == BTC_P2SH_VERSION)
+        }
+        None => false,
+    }
+}
+
+fn base58_decode(input: &[u8]) -> Option<Vec<u8>> {
+    if input.is_empty() {
+        return None;
+    }
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for c in input {
+        let mut carry = BASE58_ALPHABET.iter().position(|a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    // Leading '1's are leading zero bytes
+    for c in input {
+        if *c != b'1' {
+            break;
+        }
+        bytes.push(0);
+    }
+    bytes.reverse();
+    Some(bytes)
+}
+
+fn base58check_decode(input: &[u8]) -> Option<Vec<u8>> {
+    let decoded = base58_decode(input)?;
+    if decoded.len() < 4 {
+        return None;
+    }
+    let (payload, checksum) = decoded.split_at(decoded.len() - 4);
+    let digest = hash(&hash(payload).to_bytes()).to_bytes();
+    if digest[..4] != *checksum {
+        return None;
+    }
+    Some(payload.to_vec())
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    let mut chk: u32 = 1;
+    for v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ (*v as u32);
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn is_valid_segwit_address(to: &[u8]) -> bool {
+    if to.len() > 90 {
+        return false;
+    }
+    let has_lower = to.iter().any(|c| c.is_ascii_lowercase());
+    let has_upper = to.iter().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return false;
+    }
+    let to = to.to_ascii_lowercase();
+    let hrp_len = BTC_BECH32_HRP.len();
+
+    // 5-bit data part, without the "bc1" separator
+    let mut data: Vec<u8> = Vec::with_capacity(to.len() - hrp_len - 1);
+    for c in &to[hrp_len + 1..] {
+        match BECH32_CHARSET.iter().position(|a| a == c) {
+            Some(v) => data.push(v as u8),
+            None => return false,
+        }
+    }
+    // witness version + 6 checksum chars
+    if data.len() < 7 {
+        return false;
+    }
+
+    let mut values: Vec<u8> = Vec::with_capacity(hrp_len * 2 + 1 + data.len());
+    values.extend(BTC_BECH32_HRP.iter().map(|c| c >> 5));
+    values.push(0);
+    values.extend(BTC_BECH32_HRP.iter().map(|c| c & 31));
+    values.extend_from_slice(&data);
+    let witness_version = data[0];
+    let expected_const = if witness_version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    };
+    if witness_version > 16 || bech32_polymod(&values) != expected_const {
+        return false;
+    }
+
+    // convert the witness program from 5-bit to 8-bit groups
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut program: Vec<u8> = Vec::new();
+    for v in &data[1..data.len() - 6] {
+        acc = ((acc << 5) | (*v as u32)) & 0xfff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            program.push(((acc >> bits) & 0xff) as u8);
+        }
+    }
+    if bits >= 5 || (acc << (8 - bits)) & 0xff != 0 {
+        return false;
+    }
+
+    match witness_version {
+        0 => program.len() == 20 || program.len() == 32,
+        _ => program.len() >= 2 && program.len() <= 40,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_to_address_evm() {
+        let to = [0x11u8; 20];
+        assert!(check_to_address(1, &to).is_ok());
+        assert!(check_to_address(42161, &to).is_ok());
+        assert!(check_to_address(1, &[0x11u8; 32]).is_err());
+        assert!(check_to_address(1, &[0u8; 20]).is_err());
+    }
+
+    #[test]
+    fn test_check_to_address_pubkey() {
+        let to = Pubkey::new_unique().to_bytes();
+        assert!(check_to_address(501, &to).is_ok());
+        assert!(check_to_address(637, &to).is_ok());
+        assert!(check_to_address(784, &to).is_ok());
+        assert!(check_to_address(501, &to[..20]).is_err());
+    }
+
+    #[test]
+    fn test_check_to_address_tron() {
+        assert!(check_to_address(195, b"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t").is_ok());
+        assert!(check_to_address(195, b"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u").is_err());
+        assert!(check_to_address(195, &[0x41u8; 21]).is_err());
+    }
+
+    #[test]
+    fn test_check_to_address_btc() {
+        assert!(check_to_address(0, b"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa").is_ok());
+        assert!(check_to_address(0, b"3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy").is_ok());
+        assert!(check_to_address(0, b"BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4").is_ok());
+        assert!(check_to_address(
+            0,
+            b"bc1p5d7rjq7g6rdk2yhzks9smlaqtedr4dekq08ge8ztwac72sfr9rusxg3297"
+        )
+        .is_ok());
+        assert!(check_to_address(0, b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5").is_err());
+        assert!(check_to_address(0, b"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb").is_err());
+        // Tron address is not a bitcoin address
+        assert!(check_to_address(0, b"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t").is_err());
+    }
+
+    #[test]
+    fn test_check_to_address_unregistered_chain() {
+        // chains missing from the registry are left to the bridge adaptor
+        assert!(get_chain_family(999_999).is_none());
+        assert!(check_to_address(999_999, &[0x11u8; 20]).is_ok());
+        assert!(check_to_address(999_999, &[0x11u8; 7]).is_ok());
+        assert!(check_to_address(u64::MAX, &[]).is_ok());
+        // the same address is still checked on a registered chain
+        assert!(check_to_address(1, &[0x11u8; 7]).is_err());
+    }
+}
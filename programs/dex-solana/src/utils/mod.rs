@@ -1,3 +1,5 @@
+pub mod chain;
 pub mod token;
 
+pub use chain::*;
 pub use token::*;
@@ -1,7 +1,15 @@
 use crate::error::ErrorCode;
-use crate::{BUMP_SA, SEED_SA};
+use crate::{BUMP_SA, SEED_SA, SEED_TEMP_WSOL};
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke, system_instruction::transfer};
+use anchor_lang::solana_program::{
+    program::{invoke, invoke_signed},
+    program_pack::Pack,
+    system_instruction::{allocate, assign, create_account, transfer},
+};
+use anchor_spl::token::spl_token::instruction::{
+    close_account, initialize_account3, sync_native, transfer as token_transfer,
+};
+use anchor_spl::token::spl_token::state::Account as SplTokenAccount;
 use anchor_spl::token_2022::{self};
 
 pub fn transfer_sol_from_user<'a>(
@@ -76,3 +84,146 @@ pub fn transfer_token_from_sa_pda<'a>(
     require!(res.is_ok(), ErrorCode::TransferTokenFailed);
     Ok(())
 }
+
+pub fn transfer_sol_from_authority<'a>(
+    from: AccountInfo<'a>,
+    to: AccountInfo<'a>,
+    lamports: u64,
+) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
+    let ix = transfer(from.key, to.key, lamports);
+    let res = invoke_signed(&ix, &[from, to], &[&[SEED_SA, &[BUMP_SA]]]);
+    require!(res.is_ok(), ErrorCode::TransferSolFailed);
+    Ok(())
+}
+
+// Create the temporary wSOL account of payer owned by `owner`, returns the lamports paid by payer.
+// It is seeded by payer so that swaps of different payers don't write lock the same account
+pub fn create_temp_wsol_account<'a>(
+    payer: AccountInfo<'a>,
+    temp_wsol_account: AccountInfo<'a>,
+    wsol_mint: AccountInfo<'a>,
+    owner: &Pubkey,
+    token_program: AccountInfo<'a>,
+    system_program: AccountInfo<'a>,
+) -> Result<u64> {
+    let (temp_wsol_key, bump) =
+        Pubkey::find_program_address(&[SEED_TEMP_WSOL, payer.key.as_ref()], &crate::ID);
+    require_keys_eq!(
+        temp_wsol_account.key(),
+        temp_wsol_key,
+        ErrorCode::InvalidTempWsolAccount
+    );
+    let signer_seeds: &[&[u8]] = &[SEED_TEMP_WSOL, payer.key.as_ref(), &[bump]];
+
+    let rent = Rent::get()?.minimum_balance(SplTokenAccount::LEN);
+    let current_lamports = temp_wsol_account.lamports();
+    let lamports = rent.saturating_sub(current_lamports);
+    if current_lamports == 0 {
+        let ix = create_account(
+            payer.key,
+            temp_wsol_account.key,
+            lamports,
+            SplTokenAccount::LEN as u64,
+            token_program.key,
+        );
+        invoke_signed(
+            &ix,
+            &[payer, temp_wsol_account.clone(), system_program],
+            &[signer_seeds],
+        )?;
+    } else {
+        // Someone may have sent lamports to the temporary account address
+        transfer_sol_from_user(payer, temp_wsol_account.clone(), lamports)?;
+        let ix = allocate(temp_wsol_account.key, SplTokenAccount::LEN as u64);
+        invoke_signed(
+            &ix,
+            &[temp_wsol_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        let ix = assign(temp_wsol_account.key, token_program.key);
+        invoke_signed(
+            &ix,
+            &[temp_wsol_account.clone(), system_program],
+            &[signer_seeds],
+        )?;
+    }
+
+    let ix = initialize_account3(token_program.key, temp_wsol_account.key, wsol_mint.key, owner)?;
+    invoke(&ix, &[temp_wsol_account, wsol_mint])?;
+    Ok(lamports)
+}
+
+// Unwrap `amount` wSOL of source_token_account into lamports of authority through the temporary
+// wSOL account, returns the lamports paid by payer for the temporary account
+#[allow(clippy::too_many_arguments)]
+pub fn unwrap_wsol_to_authority<'a>(
+    payer: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    source_token_account: AccountInfo<'a>,
+    temp_wsol_account: AccountInfo<'a>,
+    wsol_mint: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    system_program: AccountInfo<'a>,
+    amount: u64,
+) -> Result<u64> {
+    let rent_paid = create_temp_wsol_account(
+        payer,
+        temp_wsol_account.clone(),
+        wsol_mint,
+        authority.key,
+        token_program.clone(),
+        system_program,
+    )?;
+
+    let ix = token_transfer(
+        token_program.key,
+        source_token_account.key,
+        temp_wsol_account.key,
+        authority.key,
+        &[],
+        amount,
+    )?;
+    let res = invoke_signed(
+        &ix,
+        &[
+            source_token_account,
+            temp_wsol_account.clone(),
+            authority.clone(),
+        ],
+        &[&[SEED_SA, &[BUMP_SA]]],
+    );
+    require!(res.is_ok(), ErrorCode::TransferTokenFailed);
+
+    let ix = close_account(
+        token_program.key,
+        temp_wsol_account.key,
+        authority.key,
+        authority.key,
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[temp_wsol_account, authority.clone(), authority],
+        &[&[SEED_SA, &[BUMP_SA]]],
+    )?;
+    Ok(rent_paid)
+}
+
+// Wrap `lamports` of authority into the wSOL destination_token_account
+pub fn wrap_sol_from_authority<'a>(
+    authority: AccountInfo<'a>,
+    destination_token_account: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    lamports: u64,
+) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
+    transfer_sol_from_authority(authority, destination_token_account.clone(), lamports)?;
+    let ix = sync_native(token_program.key, destination_token_account.key)?;
+    invoke(&ix, &[destination_token_account])?;
+    Ok(())
+}